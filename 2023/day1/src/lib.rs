@@ -0,0 +1,189 @@
+use common::{lines, Answer, ParseError, Solution};
+use std::{fmt, sync::OnceLock};
+
+mod recognizer;
+
+pub use recognizer::{Language, Match, Recognizer, Vocabulary};
+
+// the first and last digit the recognizer finds, 0 when there is none
+pub fn calibration_value(recognizer: &Recognizer, line: &str) -> u32 {
+    recognizer
+        .find_ends(line)
+        .map_or(0, |(first, last)| first.digit * 10 + last.digit)
+}
+
+pub fn part1_recognizer() -> &'static Recognizer {
+    static RECOGNIZER: OnceLock<Recognizer> = OnceLock::new();
+    RECOGNIZER.get_or_init(|| Recognizer::new(&Vocabulary::digits()))
+}
+
+// the digits and "one" to "nine"
+pub fn part2_recognizer() -> &'static Recognizer {
+    static RECOGNIZER: OnceLock<Recognizer> = OnceLock::new();
+    RECOGNIZER.get_or_init(|| {
+        let mut vocabulary = Vocabulary::digits();
+        vocabulary
+            .extend(&Vocabulary::words(Language::English, 1..=9))
+            .expect("The digits and the words don't overlap");
+        Recognizer::new(&vocabulary)
+    })
+}
+
+// how one line was read, for finding the lines that don't count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineReport<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub ends: Option<(Match, Match)>,
+}
+
+impl<'a> LineReport<'a> {
+    pub fn new(recognizer: &Recognizer, line: usize, text: &'a str) -> Self {
+        Self {
+            line,
+            text,
+            ends: recognizer.find_ends(text),
+        }
+    }
+
+    pub fn first(&self) -> Option<(&'a str, Match)> {
+        self.ends.map(|(first, _)| (first.token(self.text), first))
+    }
+
+    pub fn last(&self) -> Option<(&'a str, Match)> {
+        self.ends.map(|(_, last)| (last.token(self.text), last))
+    }
+
+    pub fn value(&self) -> u32 {
+        self.ends
+            .map_or(0, |(first, last)| first.digit * 10 + last.digit)
+    }
+
+    pub fn warning(&self) -> Option<String> {
+        match self.ends {
+            Some(_) => None,
+            None => Some(format!("Line {} has no digit: {:?}", self.line, self.text)),
+        }
+    }
+
+    // for --strict, a line that adds nothing is an error
+    pub fn check(&self) -> Result<u32, ParseError> {
+        match self.ends {
+            Some(_) => Ok(self.value()),
+            None => Err(ParseError::line(self.line, self.text, "expected a digit")),
+        }
+    }
+}
+
+impl fmt::Display for LineReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.first(), self.last()) {
+            (Some((first, f_match)), Some((last, l_match))) => write!(
+                f,
+                "line {}: {:?} at {}, {:?} at {} = {}",
+                self.line,
+                first,
+                f_match.start,
+                last,
+                l_match.start,
+                self.value()
+            ),
+            _ => write!(f, "line {}: no digit = 0", self.line),
+        }
+    }
+}
+
+pub fn report<'a>(recognizer: &Recognizer, input: &'a str) -> Vec<LineReport<'a>> {
+    lines(input)
+        .map(|(line, text)| LineReport::new(recognizer, line, text))
+        .collect()
+}
+
+pub fn parse_line_part1(line: &str) -> u32 {
+    calibration_value(part1_recognizer(), line)
+}
+
+pub fn parse_line_part2(line: &str) -> u32 {
+    calibration_value(part2_recognizer(), line)
+}
+
+pub fn part1(input: &str) -> u32 {
+    input.lines().map(parse_line_part1).sum()
+}
+
+pub fn part2(input: &str) -> u32 {
+    input.lines().map(parse_line_part2).sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse_line_part1, parse_line_part2, part1, part2, part2_recognizer, report, Match,
+    };
+
+    #[test]
+    fn test_parse_line_part1() {
+        assert_eq!(parse_line_part1("97ninesevenrhchvppnztvfbfpkzrbcone"), 97);
+        assert_eq!(parse_line_part1("treb7uchet"), 77);
+        assert_eq!(parse_line_part1("nodigits"), 0);
+    }
+
+    #[test]
+    fn test_parse_line_part2() {
+        assert_eq!(parse_line_part2("ninseven97rhchvppnztvfbfpkzrbcone"), 71);
+        assert_eq!(parse_line_part2("eightwothree"), 83);
+        assert_eq!(parse_line_part2("zoneight234"), 14);
+        assert_eq!(parse_line_part2("twone"), 21);
+    }
+
+    #[test]
+    fn test_report() {
+        let reports = report(part2_recognizer(), "xtwone3four\n\nabc\n7pqrstsixteen\n");
+        let values: Vec<u32> = reports.iter().map(|r| r.value()).collect();
+        assert_eq!(values, vec![24, 0, 0, 76]);
+        assert_eq!(
+            reports[0].first(),
+            Some((
+                "two",
+                Match {
+                    start: 1,
+                    end: 4,
+                    digit: 2
+                }
+            ))
+        );
+        assert_eq!(reports[0].last().map(|(token, m)| (token, m.start)), Some(("four", 7)));
+        assert_eq!(reports[0].to_string(), "line 1: \"two\" at 1, \"four\" at 7 = 24");
+
+        let warnings: Vec<String> = reports.iter().filter_map(|r| r.warning()).collect();
+        assert_eq!(warnings, vec!["Line 2 has no digit: \"\"", "Line 3 has no digit: \"abc\""]);
+        assert_eq!(reports[3].check(), Ok(76));
+        let e = reports[2].check().unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let input = "1abc2\na1b2c3d4é5f\nséven8twoñ\n";
+        assert_eq!(part1(input), 12 + 15 + 88);
+        assert_eq!(part2(input), 12 + 15 + 82);
+    }
+}
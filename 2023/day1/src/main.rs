@@ -1,132 +1,68 @@
-use std::char;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-fn parse_line_part1(line: &str) -> u32 {
-    let mut num = 0;
-    let line_bytes = line.as_bytes();
-    for b in line_bytes {
-        if let Some(c) = char::from(*b).to_digit(10) {
-            num = c * 10;
-            break;
-        }
-    }
-    for b in line_bytes.iter().rev() {
-        if let Some(c) = char::from(*b).to_digit(10) {
-            num += c;
-            break;
-        }
-    }
-    num
-}
+use std::{env, fs, io::BufRead, process};
 
-fn parse_digit_word(s: &str) -> Option<u32> {
-    match s {
-        "one" | "1" => Some(1),
-        "two" | "2" => Some(2),
-        "three" | "3" => Some(3),
-        "four" | "4" => Some(4),
-        "five" | "5" => Some(5),
-        "six" | "6" => Some(6),
-        "seven" | "7" => Some(7),
-        "eight" | "8" => Some(8),
-        "nine" | "9" => Some(9),
-        "0" => Some(0),
-        _ => None,
-    }
-}
+use common::{arg_value, info, warn, Source};
+use day1::{
+    part1_recognizer, part2_recognizer, LineReport, Recognizer, Vocabulary,
+};
 
-fn parse_line_part2(line: &str) -> u32 {
-    let mut num = 0;
-    for idx in 0..line.len() {
-        if let Some(c) = parse_digit_word(&line[idx..idx+1]) {
-            num = c * 10;
-            break;
-        }
-        if idx >= 2 {
-            if let Some(c) = parse_digit_word(&line[idx - 2..idx + 1]) {
-                num = c * 10;
-                break;
-            }
-        }
-        if idx >= 3 {
-            if let Some(c) = parse_digit_word(&line[idx - 3..idx + 1]) {
-                num = c * 10;
-                break;
-            }
-        }
-        if idx >= 4 {
-            if let Some(c) = parse_digit_word(&line[idx - 4..idx + 1]) {
-                num = c * 10;
-                break;
-            }
-        }
+// `--words english,french` swaps the part 2 words for those languages, with
+// `zero` among them for 0 too (english when it comes alone), and
+// `--vocab <file>` adds token=digit lines
+fn recognizer() -> Option<Recognizer> {
+    let words = arg_value("--words");
+    let vocab = arg_value("--vocab");
+    if words.is_none() && vocab.is_none() {
+        return None;
     }
-
-    for idx in (0..line.len()).rev() {
-        if let Some(c) = parse_digit_word(&line[idx..idx+1]) {
-            num += c;
-            break;
-        }
-        if idx + 2 < line.len() {
-            if let Some(c) = parse_digit_word(&line[idx..idx + 3]) {
-                num += c;
-                break;
-            }
-        }
-        if idx + 3 < line.len() {
-            if let Some(c) = parse_digit_word(&line[idx..idx + 4]) {
-                num += c;
-                break;
-            }
-        }
-        if idx + 4 < line.len() {
-            if let Some(c) = parse_digit_word(&line[idx..idx + 5]) {
-                num += c;
-                break;
-            }
-        }
+    let mut vocabulary = Vocabulary::digits();
+    let names = words.unwrap_or_else(|| "english".to_string());
+    let words = Vocabulary::from_words(&names).expect("Invalid --words.");
+    vocabulary.extend(&words).expect("Failed to add the words.");
+    if let Some(path) = vocab {
+        let text = fs::read_to_string(path).expect("Failed to read the vocabulary file.");
+        let table = Vocabulary::parse(&text).expect("Failed to parse the vocabulary file.");
+        vocabulary.extend(&table).expect("Failed to add the vocabulary.");
     }
-    num
+    Some(Recognizer::new(&vocabulary))
 }
 
+// `--strict` stops at the first line without a digit, otherwise such lines
+// count for nothing and are only reported with -v
 fn main() {
-    let file = File::open("./input").expect("Failed to open input file");
-    let mut buf_reader = BufReader::new(file);
-    let mut line = String::new();
-    let mut sum_part1: u32 = 0;
-    let mut sum_part2: u32 = 0;
-    loop {
-        match buf_reader.read_line(&mut line) {
-            Ok(len) => {
-                if len == 0 {
-                    break;
+    let strict = env::args().any(|arg| arg == "--strict");
+    let source = Source::from_args_with(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input"),
+        &["--words", "--vocab"],
+    );
+    let custom = recognizer();
+    let recognizers = [
+        part1_recognizer(),
+        custom.as_ref().unwrap_or_else(|| part2_recognizer()),
+    ];
+    let mut buf_reader = source.open().expect("Failed to open input file.");
+    let mut text = String::new();
+    let mut sums: [u32; 2] = [0, 0];
+    for line in 1.. {
+        text.clear();
+        let len = buf_reader
+            .read_line(&mut text)
+            .expect("Failed to read input file.");
+        if len == 0 {
+            break;
+        }
+        for (part, recognizer) in recognizers.iter().enumerate() {
+            let report = LineReport::new(recognizer, line, text.trim_end_matches(['\n', '\r']));
+            info!("Part{} {}", part + 1, report);
+            match report.check() {
+                Ok(value) => sums[part] += value,
+                Err(e) if strict => {
+                    eprintln!("Part{}: {}", part + 1, e);
+                    process::exit(1);
                 }
-                sum_part1 += parse_line_part1(&line);
-                sum_part2 += parse_line_part2(&line);
-                line.clear();
+                Err(_) => warn!("Part{}: {}", part + 1, report.warning().unwrap_or_default()),
             }
-            Err(_) => break,
         }
     }
-    println!("{}", sum_part1);
-    println!("{}", sum_part2);
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{parse_line_part1, parse_line_part2};
-
-    #[test]
-    fn test_parse_line_part1() {
-        let line = "97ninesevenrhchvppnztvfbfpkzrbcone";
-        let sum = parse_line_part1(line);
-        println!("{}", sum);
-    }
-
-    #[test]
-    fn test_parse_line_part2() {
-        let line = "ninseven97rhchvppnztvfbfpkzrbcone";
-        let sum = parse_line_part2(line);
-        println!("{}", sum);
-    }
+    println!("Part1 {}", sums[0]);
+    println!("Part2 {}", sums[1]);
 }
@@ -0,0 +1,326 @@
+use std::{collections::VecDeque, fmt, ops::RangeInclusive};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    fn words(self) -> [&'static str; 10] {
+        match self {
+            Self::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Self::French => [
+                "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+            Self::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Self::Spanish => [
+                "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "english" | "en" => Some(Self::English),
+            "french" | "fr" => Some(Self::French),
+            "german" | "de" => Some(Self::German),
+            "spanish" | "es" => Some(Self::Spanish),
+            _ => None,
+        }
+    }
+}
+
+// the tokens that stand for a digit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    tokens: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // "0" to "9"
+    pub fn digits() -> Self {
+        let mut vocabulary = Self::new();
+        for digit in 0..=9 {
+            vocabulary.tokens.push((digit.to_string(), digit));
+        }
+        vocabulary
+    }
+
+    // `words(Language::English, 1..=9)` is "one" to "nine", the part 2 words
+    pub fn words(language: Language, digits: RangeInclusive<u32>) -> Self {
+        let words = language.words();
+        let mut vocabulary = Self::new();
+        for digit in digits.filter(|d| *d <= 9) {
+            vocabulary.tokens.push((words[digit as usize].to_string(), digit));
+        }
+        vocabulary
+    }
+
+    // "english,french" as `--words` takes it, "zero" adds the word for 0 to
+    // each of them, alone it stands for "english,zero"
+    pub fn from_words(names: &str) -> Result<Self, String> {
+        let names: Vec<&str> = names.split(',').map(str::trim).collect();
+        let digits = if names.contains(&"zero") { 0..=9 } else { 1..=9 };
+        let mut languages = names
+            .iter()
+            .filter(|name| **name != "zero")
+            .map(|name| {
+                Language::from_name(name).ok_or_else(|| format!("Unknown language '{}'", name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if languages.is_empty() {
+            languages.push(Language::English);
+        }
+        let mut vocabulary = Self::new();
+        for language in languages {
+            vocabulary.extend(&Self::words(language, digits.clone()))?;
+        }
+        Ok(vocabulary)
+    }
+
+    pub fn add(&mut self, token: &str, digit: u32) -> Result<(), String> {
+        if token.is_empty() {
+            return Err("Empty token".to_string());
+        }
+        if digit > 9 {
+            return Err(format!("'{}' is not a digit for '{}'", digit, token));
+        }
+        match self.tokens.iter().find(|(t, _)| t == token) {
+            Some((_, d)) if *d == digit => Ok(()),
+            Some((_, d)) => Err(format!("'{}' is already {}, not {}", token, d, digit)),
+            None => {
+                self.tokens.push((token.to_string(), digit));
+                Ok(())
+            }
+        }
+    }
+
+    pub fn extend(&mut self, other: &Vocabulary) -> Result<(), String> {
+        for (token, digit) in other.tokens.iter() {
+            self.add(token, *digit)?;
+        }
+        Ok(())
+    }
+
+    // one `token=digit` per line or comma, e.g. "uno=1, dos=2"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut vocabulary = Self::new();
+        for entry in text.split([',', '\n']).map(str::trim) {
+            if entry.is_empty() {
+                continue;
+            }
+            let (token, digit) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Expected token=digit, got '{}'", entry))?;
+            let digit = digit
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid digit in '{}'", entry))?;
+            vocabulary.add(token.trim(), digit)?;
+        }
+        Ok(vocabulary)
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.tokens.iter().map(|(t, d)| (t.as_str(), *d))
+    }
+}
+
+// a token found in a line, `start..end` are byte offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub digit: u32,
+}
+
+impl Match {
+    pub fn token<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.end]
+    }
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.digit, self.start)
+    }
+}
+
+const ROOT: usize = 0;
+
+// an Aho-Corasick automaton over the bytes of the tokens. Tokens are valid
+// UTF-8, so every match starts and ends on a char boundary
+#[derive(Debug, Clone)]
+pub struct Recognizer {
+    // a full transition table, the failure links are folded in
+    next: Vec<[u32; 256]>,
+    // the token that ends at the state, as (length, digit)
+    token: Vec<Option<(usize, u32)>>,
+    // the closest state on the failure path that ends a token
+    output: Vec<Option<u32>>,
+}
+
+impl Recognizer {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut next = vec![[u32::MAX; 256]];
+        let mut token = vec![None];
+        for (text, digit) in vocabulary.tokens() {
+            let mut state = ROOT;
+            for &b in text.as_bytes() {
+                if next[state][b as usize] == u32::MAX {
+                    next[state][b as usize] = next.len() as u32;
+                    next.push([u32::MAX; 256]);
+                    token.push(None);
+                }
+                state = next[state][b as usize] as usize;
+            }
+            token[state] = Some((text.len(), digit));
+        }
+
+        // breadth first, so the failure of a state is done before its children
+        let mut fail = vec![ROOT; next.len()];
+        let mut output = vec![None; next.len()];
+        let mut queue = VecDeque::new();
+        for b in 0..256 {
+            match next[ROOT][b] {
+                u32::MAX => next[ROOT][b] = ROOT as u32,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let f = fail[state];
+            output[state] = if token[f].is_some() {
+                Some(f as u32)
+            } else {
+                output[f]
+            };
+            for b in 0..256 {
+                match next[state][b] {
+                    u32::MAX => next[state][b] = next[f][b],
+                    child => {
+                        fail[child as usize] = next[f][b] as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+        Self {
+            next,
+            token,
+            output,
+        }
+    }
+
+    // every token in the line, overlapping ones included, by where they end
+    pub fn find_all(&self, line: &str) -> Vec<Match> {
+        let mut matches = Vec::new();
+        self.scan(line, |m| matches.push(m));
+        matches
+    }
+
+    // the tokens that start first and last, in one pass. Of two tokens at the
+    // same place the longer one wins
+    pub fn find_ends(&self, line: &str) -> Option<(Match, Match)> {
+        let mut ends: Option<(Match, Match)> = None;
+        self.scan(line, |m| match ends.as_mut() {
+            None => ends = Some((m, m)),
+            Some((first, last)) => {
+                if m.start < first.start || (m.start == first.start && m.end > first.end) {
+                    *first = m;
+                }
+                if m.start > last.start || (m.start == last.start && m.end > last.end) {
+                    *last = m;
+                }
+            }
+        });
+        ends
+    }
+
+    fn scan(&self, line: &str, mut found: impl FnMut(Match)) {
+        let mut state = ROOT;
+        for (idx, &b) in line.as_bytes().iter().enumerate() {
+            state = self.next[state][b as usize] as usize;
+            let end = idx + 1;
+            // the token of the state itself, then the shorter ones it ends with
+            let mut at = match self.token[state] {
+                Some(_) => Some(state),
+                None => self.output[state].map(|s| s as usize),
+            };
+            while let Some(s) = at {
+                let (len, digit) = self.token[s].expect("Output states end a token");
+                found(Match {
+                    start: end - len,
+                    end,
+                    digit,
+                });
+                at = self.output[s].map(|s| s as usize);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Language, Recognizer, Vocabulary};
+
+    fn part2() -> Recognizer {
+        let mut vocabulary = Vocabulary::digits();
+        vocabulary
+            .extend(&Vocabulary::words(Language::English, 1..=9))
+            .unwrap();
+        Recognizer::new(&vocabulary)
+    }
+
+    fn digits(recognizer: &Recognizer, line: &str) -> Vec<u32> {
+        recognizer.find_all(line).iter().map(|m| m.digit).collect()
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let recognizer = part2();
+        assert_eq!(digits(&recognizer, "oneight"), vec![1, 8]);
+        assert_eq!(digits(&recognizer, "twone3"), vec![2, 1, 3]);
+        assert_eq!(digits(&recognizer, "sevenine"), vec![7, 9]);
+        let (first, last) = recognizer.find_ends("xtwone3four").unwrap();
+        assert_eq!((first.digit, first.start, first.token("xtwone3four")), (2, 1, "two"));
+        assert_eq!((last.digit, last.start, last.token("xtwone3four")), (4, 7, "four"));
+        assert_eq!(recognizer.find_ends("zero"), None);
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let mut vocabulary = Vocabulary::words(Language::French, 0..=9);
+        vocabulary.extend(&Vocabulary::parse("uno=1, dos = 2\ntres=3").unwrap()).unwrap();
+        let recognizer = Recognizer::new(&vocabulary);
+        // the matches land on char boundaries, even after multibyte chars
+        let line = "ñzérodosfünfneuf";
+        assert_eq!(digits(&recognizer, line), vec![0, 2, 9]);
+        let (first, last) = recognizer.find_ends(line).unwrap();
+        assert_eq!((first.token(line), last.token(line)), ("zéro", "neuf"));
+
+        assert!(vocabulary.add("un", 2).is_err());
+        assert!(vocabulary.add("un", 1).is_ok());
+        assert!(vocabulary.add("", 1).is_err());
+        assert!(Vocabulary::parse("ten=10").is_err());
+        assert!(Vocabulary::parse("ten").is_err());
+
+        // "zero" alone is the english words with zero
+        let zero = Vocabulary::from_words("zero").unwrap();
+        assert_eq!(zero, Vocabulary::words(Language::English, 0..=9));
+        let both = Vocabulary::from_words("fr, zero,de").unwrap();
+        assert_eq!(both.tokens().filter(|(_, d)| *d == 0).count(), 2);
+        assert_eq!(Vocabulary::from_words("english").unwrap().tokens().count(), 9);
+        assert!(Vocabulary::from_words("klingon").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use common::{Answer, ParseError, Solution};
+use grid::{Cell, Direction, Grid, Picture, Pos, Rgb};
+use std::{error::Error, fmt::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PipeGrid {
+    // S
+    Start,
+    // .
+    Ground,
+    // |
+    NorthSouth,
+    // -
+    EastWest,
+    // L
+    NorthEast,
+    // J
+    NorthWest,
+    // 7
+    SouthWest,
+    // F
+    SouthEast,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsePipeGridError {
+    found: char,
+}
+
+impl Display for ParsePipeGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid pipe {:?}", self.found)
+    }
+}
+
+impl Error for ParsePipeGridError {}
+
+impl TryFrom<char> for PipeGrid {
+    type Error = ParsePipeGridError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'S' => Ok(Self::Start),
+            '.' => Ok(Self::Ground),
+            '|' => Ok(Self::NorthSouth),
+            '-' => Ok(Self::EastWest),
+            'L' => Ok(Self::NorthEast),
+            'J' => Ok(Self::NorthWest),
+            '7' => Ok(Self::SouthWest),
+            'F' => Ok(Self::SouthEast),
+            _ => Err(ParsePipeGridError { found: c }),
+        }
+    }
+}
+
+impl Cell for PipeGrid {
+    fn from_char(c: char) -> Option<Self> {
+        c.try_into().ok()
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Start => 'S',
+            Self::Ground => '.',
+            Self::NorthSouth => '|',
+            Self::EastWest => '-',
+            Self::NorthEast => 'L',
+            Self::NorthWest => 'J',
+            Self::SouthWest => '7',
+            Self::SouthEast => 'F',
+        }
+    }
+}
+
+impl PipeGrid {
+    // the two sides a pipe connects, None for the start and the ground
+    fn connections(&self) -> Option<[Direction; 2]> {
+        match self {
+            Self::Start | Self::Ground => None,
+            Self::NorthSouth => Some([Direction::North, Direction::South]),
+            Self::EastWest => Some([Direction::East, Direction::West]),
+            Self::NorthEast => Some([Direction::North, Direction::East]),
+            Self::NorthWest => Some([Direction::North, Direction::West]),
+            Self::SouthWest => Some([Direction::South, Direction::West]),
+            Self::SouthEast => Some([Direction::South, Direction::East]),
+        }
+    }
+}
+
+pub struct Game {
+    lines: Grid<PipeGrid>,
+    start: Option<Pos>,
+    profile: Option<Grid<PipeGrid>>,
+    path: Vec<Pos>,
+    distance: Option<usize>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self {
+            lines: Grid::default(),
+            start: None,
+            profile: None,
+            path: Vec::new(),
+            distance: None,
+        }
+    }
+
+    pub fn insert_new_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let line_no = self.lines.rows() + 1;
+        let text = line.trim();
+        let mut grids = Vec::with_capacity(text.len());
+        for (x, c) in text.chars().enumerate() {
+            let grid: PipeGrid = c.try_into().map_err(|e: ParsePipeGridError| {
+                ParseError::new(line_no, x + 1, c.to_string(), e.to_string())
+            })?;
+            if grid == PipeGrid::Start {
+                let y = self.lines.rows();
+                if let Some(start) = self.start.replace(Pos::new(y, x)) {
+                    return Err(ParseError::new(
+                        line_no,
+                        x + 1,
+                        "S",
+                        format!("the start is already at line {} column {}", start.row + 1, start.col + 1),
+                    ));
+                }
+            }
+            grids.push(grid);
+        }
+        self.lines.push_row(line_no, grids)?;
+        Ok(())
+    }
+
+    // 0 when there is no start, see `trace_loop` for an error instead
+    pub fn play_part1(&mut self) -> usize {
+        let Some(start) = self.start else {
+            return 0;
+        };
+        let mut max_distance = 0;
+        for direction in [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ] {
+            if let Some(next) = self.lines.step(start, direction) {
+                if let Some(lp) = self.try_find_loop(start, next) {
+                    if max_distance < lp.len() / 2 {
+                        max_distance = lp.len() / 2;
+                        self.draw_profile(&lp);
+                    }
+                }
+            }
+        }
+        self.distance.replace(max_distance);
+        max_distance
+    }
+
+    // part 1, an error pointing at the start when it isn't on a loop, as in
+    // a truncated input
+    pub fn trace_loop(&mut self) -> Result<usize, ParseError> {
+        let Some(start) = self.start else {
+            return Err(ParseError::eof(self.lines.rows(), "expected a start S"));
+        };
+        let distance = self.play_part1();
+        if self.profile.is_none() {
+            return Err(ParseError::new(
+                start.row + 1,
+                start.col + 1,
+                "S",
+                "the start is not on a closed loop",
+            ));
+        }
+        Ok(distance)
+    }
+
+    fn draw_profile(&mut self, lp: &[Pos]) {
+        let mut profile = Grid::new(self.lines.rows(), self.lines.cols(), PipeGrid::Ground);
+        for grid in lp.iter() {
+            profile[*grid] = self.lines[*grid];
+        }
+        let start = self.start.unwrap();
+        // the start is linked to the neighbours whose pipe points back to it
+        let mut linked = Vec::new();
+        for direction in Direction::ALL {
+            if let Some(neb) = self.lines.step(start, direction) {
+                if let Some(connections) = profile[neb].connections() {
+                    if connections.contains(&direction.opposite()) {
+                        linked.push(direction);
+                    }
+                }
+            }
+        }
+        profile[start] = [
+            PipeGrid::NorthSouth,
+            PipeGrid::EastWest,
+            PipeGrid::NorthEast,
+            PipeGrid::NorthWest,
+            PipeGrid::SouthWest,
+            PipeGrid::SouthEast,
+        ]
+        .into_iter()
+        .find(|pipe| {
+            let connections = pipe.connections().unwrap();
+            linked.len() == 2 && linked.iter().all(|d| connections.contains(d))
+        })
+        .expect("Invalid start grid.");
+        self.profile.replace(profile);
+        self.path = lp.to_vec();
+    }
+
+    fn try_find_loop(&self, start: Pos, cur: Pos) -> Option<Vec<Pos>> {
+        let mut lp = vec![start];
+        let mut cur = cur;
+        let mut pre = start;
+        while let Some(next) = self.get_next_grid(pre, cur) {
+            lp.push(cur);
+            pre = cur;
+            cur = next;
+            if cur == start {
+                break;
+            }
+        }
+        if cur == start {
+            Some(lp)
+        } else {
+            None
+        }
+    }
+
+    pub fn play_part2(&self) -> usize {
+        self.inside_tiles().len()
+    }
+
+    fn inside_tiles(&self) -> Vec<Pos> {
+        let Some(profile) = self.profile.as_ref() else {
+            return Vec::new();
+        };
+        let mut inside = Vec::new();
+        for row in 0..profile.rows() {
+            let mut in_loop = false;
+            let mut pre = PipeGrid::Ground;
+            for (col, grid) in profile.row(row).iter().enumerate() {
+                match grid {
+                    PipeGrid::EastWest => continue,
+                    PipeGrid::NorthSouth | PipeGrid::NorthEast | PipeGrid::SouthEast => {
+                        in_loop = !in_loop;
+                    }
+                    PipeGrid::NorthWest => {
+                        if pre == PipeGrid::NorthEast {
+                            in_loop = !in_loop;
+                        }
+                    }
+                    PipeGrid::SouthWest => {
+                        if pre == PipeGrid::SouthEast {
+                            in_loop = !in_loop;
+                        }
+                    }
+                    _ => {
+                        if in_loop {
+                            inside.push(Pos::new(row, col));
+                        }
+                    }
+                }
+                pre = *grid;
+            }
+        }
+        inside
+    }
+
+    // the loop over the pipes, with the tiles it encloses highlighted
+    pub fn render(&self) -> Picture {
+        let profile = self.profile.as_ref().unwrap_or(&self.lines);
+        let mut picture = Picture::from_grid(profile, |_, grid| match grid {
+            PipeGrid::Ground => Rgb::DARK,
+            _ => Rgb::GREY,
+        });
+        for pos in self.inside_tiles() {
+            picture.fill(pos, Rgb::YELLOW);
+        }
+        let mut path = self.path.clone();
+        path.extend(self.path.first());
+        picture.path(&path, Rgb::BLUE);
+        picture
+    }
+
+    // follow the pipe at `cur`, coming from `pre`
+    fn get_next_grid(&self, pre: Pos, cur: Pos) -> Option<Pos> {
+        let [a, b] = self.lines.get(cur)?.connections()?;
+        let a = self.lines.step(cur, a)?;
+        let b = self.lines.step(cur, b)?;
+        if pre == a {
+            Some(b)
+        } else if pre == b {
+            Some(a)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn read_game(input: &str) -> Result<Game, ParseError> {
+    let mut game = Game::new();
+    for line in input.lines() {
+        game.insert_new_line(line)?;
+    }
+    game.trace_loop()?;
+    Ok(game)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Game;
+
+    // both parts need the loop, reading traces it once up front
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_game(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.distance.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.play_part2().into()
+    }
+}
@@ -0,0 +1,171 @@
+use common::{Answer, ParseError, Solution};
+use std::io::BufRead;
+
+#[cfg(test)]
+mod oracle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Index(usize, usize); // column idx, row idx
+
+pub struct Game {
+    galaxies: Vec<Index>,
+    // the galaxies count in a column
+    cols: Vec<usize>,
+    // the galaxies count in a row
+    rows: Vec<usize>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self {
+            galaxies: Vec::new(),
+            cols: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn read_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let text = line.trim();
+        let line_no = self.rows.len() + 1;
+        let line = text.as_bytes();
+        if self.cols.is_empty() {
+            self.cols = [0].repeat(line.len());
+        } else if line.len() != self.cols.len() {
+            return Err(ParseError::new(
+                line_no,
+                line.len().min(self.cols.len()) + 1,
+                text,
+                format!("expected {} columns, found {}", self.cols.len(), line.len()),
+            ));
+        }
+        let r = self.rows.len();
+        self.rows.push(0);
+        for (c, found) in text.char_indices() {
+            match found {
+                '#' => {
+                    self.galaxies.push(Index(r, c));
+                    self.cols[c] += 1;
+                    self.rows[r] += 1;
+                }
+                '.' => {}
+                _ => {
+                    let found = &text[c..c + found.len_utf8()];
+                    return Err(ParseError::at(line_no, text, found, "expected '.' or '#'"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn part(&self, expand: usize) -> usize {
+        let mut row_row_distance = Vec::with_capacity(self.rows.len());
+        let mut row = [0_usize].repeat(self.rows.len());
+        if self.rows[0] == 0 {
+            row[0] = expand
+        } else {
+            row[0] = 1
+        }
+        for i in 1..self.rows.len() {
+            row[i] = if self.rows[i] == 0 {
+                row[i - 1] + expand
+            } else {
+                row[i - 1] + 1
+            };
+        }
+        row_row_distance.push(row);
+        for i in 1..self.rows.len() {
+            let delta = if self.rows[i-1] == 0 { expand } else { 1 };
+            let mut row: Vec<usize> = row_row_distance
+                .last()
+                .unwrap()
+                .iter()
+                .map(|v| (*v).saturating_sub(delta))
+                .collect();
+            for j in 0..i {
+                row[j] = row_row_distance[j][i];
+            }
+            row_row_distance.push(row);
+        }
+
+        let mut col_col_distance = Vec::with_capacity(self.cols.len());
+        let mut col = [0_usize].repeat(self.cols.len());
+        if self.cols[0] == 0 {
+            col[0] = expand
+        } else {
+            col[0] = 1
+        }
+        for i in 1..self.cols.len() {
+            col[i] = if self.cols[i] == 0 {
+                col[i-1] + expand
+            } else {
+                col[i-1] + 1
+            };
+        }
+        col_col_distance.push(col); 
+        for i in 1..self.cols.len() {
+            let delta = if self.cols[i - 1] == 0 { expand } else { 1 };
+            let mut col: Vec<usize> = col_col_distance.last().unwrap().iter().map(|v| (*v).saturating_sub(delta)).collect();
+            for j in 0..i {
+                col[j] = col_col_distance[j][i];
+            }
+            col_col_distance.push(col);
+        }
+
+        let mut sum = 0;
+
+        for g_i in 0..self.galaxies.len() {
+            let g1 = self.galaxies.get(g_i).unwrap();
+            for g_j in g_i+1..self.galaxies.len() {
+                let g2 = self.galaxies.get(g_j).unwrap();
+                let distance = row_row_distance[g1.0][g2.0] + col_col_distance[g1.1][g2.1] - 2;
+                sum += distance;
+            }
+        }
+        sum
+    }
+}
+
+pub fn read_game(input: &str) -> Result<Game, ParseError> {
+    stream_game(input.as_bytes())
+}
+
+// one line at a time, the image is never held as text
+pub fn stream_game<R: BufRead>(reader: R) -> Result<Game, ParseError> {
+    let mut game = Game::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| ParseError::io(idx + 1, e))?;
+        game.read_line(&line)?;
+    }
+    if game.rows.is_empty() {
+        return Err(ParseError::eof(1, "expected the image"));
+    }
+    Ok(game)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_game(input)
+    }
+
+    fn read<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        stream_game(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part(2).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part(1000000).into()
+    }
+}
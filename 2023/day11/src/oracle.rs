@@ -0,0 +1,66 @@
+// random images, and pairwise distances counted on the expanded coordinates
+// to check the distance matrices of `Game::part`
+use common::Rng;
+
+use crate::read_game;
+
+pub fn generate(rng: &mut Rng) -> String {
+    let rows = rng.range(1, 10) as usize;
+    let cols = rng.range(1, 10) as usize;
+    let density = rng.range(2, 8);
+    let mut text = String::new();
+    for _ in 0..rows {
+        for _ in 0..cols {
+            text.push(if rng.one_in(density) { '#' } else { '.' });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+pub fn brute_force(text: &str, expand: usize) -> usize {
+    let image: Vec<&[u8]> = text.lines().map(|l| l.as_bytes()).collect();
+    let empty_rows: Vec<bool> = image.iter().map(|r| !r.contains(&b'#')).collect();
+    let empty_cols: Vec<bool> = (0..image[0].len())
+        .map(|c| image.iter().all(|r| r[c] != b'#'))
+        .collect();
+    // where every galaxy lands once the empty rows and columns grew
+    let mut galaxies = Vec::new();
+    let mut row = 0;
+    for r in 0..image.len() {
+        let mut col = 0;
+        for c in 0..image[r].len() {
+            if image[r][c] == b'#' {
+                galaxies.push((row, col));
+            }
+            col += if empty_cols[c] { expand } else { 1 };
+        }
+        row += if empty_rows[r] { expand } else { 1 };
+    }
+    let mut sum = 0;
+    for i in 0..galaxies.len() {
+        for j in i + 1..galaxies.len() {
+            sum += galaxies[i].0.abs_diff(galaxies[j].0) + galaxies[i].1.abs_diff(galaxies[j].1);
+        }
+    }
+    sum
+}
+
+#[test]
+fn test_part() {
+    for case in 0..3000 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let game = read_game(&text).expect("Invalid generated image");
+        for expand in [1, 2, 10, 1000000] {
+            assert_eq!(
+                game.part(expand),
+                brute_force(&text, expand),
+                "case {} expand {}:\n{}",
+                case,
+                expand,
+                text
+            );
+        }
+    }
+}
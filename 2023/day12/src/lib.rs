@@ -0,0 +1,171 @@
+use common::{lines, par_map, parse_number, Answer, ParseError, Solution};
+
+#[cfg(test)]
+mod oracle;
+
+#[derive(Debug, Clone, Copy, Hash)]
+enum SprintStatus {
+    Normal,
+    Broken,
+}
+
+pub struct Record {
+    sprints: Vec<Option<SprintStatus>>,
+    nums: Vec<u8>,
+}
+
+impl Record {
+    // `line` is only used to report errors
+    pub fn build(line: usize, input: &str) -> Result<Self, ParseError> {
+        let pieces: Vec<&str> = input.split_whitespace().collect();
+        if pieces.len() != 2 {
+            return Err(ParseError::line(line, input, "expected the springs and the group sizes"));
+        }
+        let mut sprints = Vec::with_capacity(pieces[0].len());
+        for (idx, c) in pieces[0].char_indices() {
+            sprints.push(match c {
+                '.' => Some(SprintStatus::Normal),
+                '#' => Some(SprintStatus::Broken),
+                '?' => None,
+                _ => {
+                    let found = &pieces[0][idx..idx + c.len_utf8()];
+                    return Err(ParseError::at(line, input, found, "expected '.', '#' or '?'"));
+                }
+            });
+        }
+        let nums = pieces[1]
+            .split(',')
+            .map(|n| parse_number(line, input, n))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { sprints, nums })
+    }
+}
+
+
+impl Record {
+    
+    pub fn possibility(&self) -> usize {
+        let mut pre_0: Vec<usize> = [0].repeat(self.sprints.len());
+        let mut pre_1: Vec<usize> = [0].repeat(self.sprints.len());
+        for i in 0..self.sprints.len() {
+            match self.sprints[i] {
+             Some(SprintStatus::Normal) | None => {
+                pre_0[i] = 1;
+             },
+             _ => {
+                break
+             } 
+            }
+        }
+        for num_idx in 0..self.nums.len() {
+            // let num = (*num) as usize;
+            let mut linked = 0;
+            while linked < self.nums[num_idx] {
+                linked += 1;
+                let mut cur_0: Vec<usize> = [0].repeat(self.sprints.len());
+                let mut cur_1: Vec<usize> = [0].repeat(self.sprints.len());
+                match self.sprints[0] {
+                    Some(SprintStatus::Normal) => {
+                        cur_0[0] = 0;
+                        cur_1[0] = 0;
+                    },
+                    Some(SprintStatus::Broken) | None => {
+                        if linked == 1 {
+                            if num_idx == 0 {
+                                cur_1[0] = 1;
+                            } else {
+                                cur_1[0] = 0;
+                            }
+                        } else {
+                            cur_1[0] = 0;
+                        }
+                        cur_0[0] = 0;
+                    },
+                }
+
+                for i in 1..self.sprints.len() {
+                    match self.sprints[i] {
+                        Some(SprintStatus::Normal) => {
+                            cur_0[i] = cur_0[i-1] + cur_1[i-1];
+                            cur_1[i] = 0;
+                        },
+                        Some(SprintStatus::Broken) => {
+                            if linked == 1 {
+                                cur_1[i] = pre_0[i-1];
+                            } else {
+                                cur_1[i] = pre_1[i-1];
+                            }
+                            cur_0[i] = 0;
+                        },
+                        None => {
+                            cur_0[i] = cur_0[i-1] + cur_1[i-1];
+                            if linked == 1 {
+                                cur_1[i] = pre_0[i-1];
+                            } else {
+                                cur_1[i] = pre_1[i-1];
+                            }
+                        }
+                    }
+                }
+                pre_0 = cur_0;
+                pre_1 = cur_1;
+            }
+        }
+
+        match self.sprints.last().unwrap() {
+            Some(SprintStatus::Normal) => {
+                *pre_0.last().unwrap()
+            },
+            Some(SprintStatus::Broken) => {
+                *pre_1.last().unwrap()
+            },
+            None => {
+                *pre_0.last().unwrap() + *pre_1.last().unwrap()
+            }
+        }
+    }
+
+    pub fn unfold(&self) -> Self {
+        let mut sprints = self.sprints.clone();
+        let nums = self.nums.clone().repeat(5);
+        for _ in 0..4 {
+            sprints.push(None);
+            sprints.extend(self.sprints.iter());
+        }
+        Self { sprints, nums }
+    }
+
+}
+
+pub fn read_records(input: &str) -> Result<Vec<Record>, ParseError> {
+    lines(input)
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(line, text)| Record::build(line, text))
+        .collect()
+}
+
+pub fn part1(records: &[Record]) -> usize {
+    par_map(records, |r| r.possibility()).into_iter().sum()
+}
+
+pub fn part2(records: &[Record]) -> usize {
+    par_map(records, |r| r.unfold().possibility()).into_iter().sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Record>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_records(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
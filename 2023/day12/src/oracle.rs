@@ -0,0 +1,81 @@
+// random records, and a brute force count to check `Record::possibility`
+use common::Rng;
+
+use crate::{Record, SprintStatus};
+
+fn groups(springs: &[bool]) -> Vec<u8> {
+    let mut groups = Vec::new();
+    let mut run = 0;
+    for &broken in springs.iter().chain([false].iter()) {
+        if broken {
+            run += 1;
+        } else if run > 0 {
+            groups.push(run);
+            run = 0;
+        }
+    }
+    groups
+}
+
+// a random row of springs with some of them hidden behind `?`, the groups
+// usually come from the row itself but sometimes match nothing at all
+pub fn generate(rng: &mut Rng) -> String {
+    let len = rng.range(1, 13) as usize;
+    let mut springs: Vec<bool> = (0..len).map(|_| rng.one_in(2)).collect();
+    if !springs.contains(&true) {
+        springs[rng.below(len)] = true;
+    }
+    let mut nums = groups(&springs);
+    if rng.one_in(4) {
+        let idx = rng.below(nums.len());
+        nums[idx] = rng.range(1, 4) as u8;
+    }
+    let row: String = springs
+        .iter()
+        .map(|&broken| match (rng.one_in(2), broken) {
+            (true, _) => '?',
+            (false, true) => '#',
+            (false, false) => '.',
+        })
+        .collect();
+    let nums: Vec<String> = nums.iter().map(|n| n.to_string()).collect();
+    format!("{} {}", row, nums.join(","))
+}
+
+// tries every way of filling in the unknown springs
+pub fn brute_force(record: &Record) -> usize {
+    let unknown: Vec<usize> = (0..record.sprints.len())
+        .filter(|&i| record.sprints[i].is_none())
+        .collect();
+    let mut count = 0;
+    for mask in 0..1_u32 << unknown.len() {
+        let mut springs: Vec<bool> = record
+            .sprints
+            .iter()
+            .map(|s| matches!(s, Some(SprintStatus::Broken)))
+            .collect();
+        for (bit, &i) in unknown.iter().enumerate() {
+            springs[i] = mask & (1 << bit) != 0;
+        }
+        if groups(&springs) == record.nums {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_possibility() {
+    for case in 0..5000 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let record = Record::build(1, &text).expect("Invalid generated record");
+        assert_eq!(
+            record.possibility(),
+            brute_force(&record),
+            "case {}: {}",
+            case,
+            text
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use common::{lines, warn, Answer, ParseError, Solution};
+
+pub struct Graph {
+    rows: Vec<u32>,
+    cols: Vec<u32>,
+}
+
+impl Graph {
+
+    fn new() -> Self {
+        Self { rows: Vec::new(), cols: Vec::new()}
+    }
+
+    // rows and columns are packed into u32 bits, so a pattern is at most 32x32
+    fn add_line(&mut self, line_no: usize, text: &str) -> Result<(), ParseError> {
+        let line = text.as_bytes();
+        if self.cols.is_empty() {
+            if line.len() > 32 {
+                return Err(ParseError::new(line_no, 33, "", "pattern is wider than 32"));
+            }
+            self.cols = [0].repeat(line.len());
+        } else if line.len() != self.cols.len() {
+            return Err(ParseError::new(
+                line_no,
+                line.len().min(self.cols.len()) + 1,
+                "",
+                format!("expected {} cells, found {}", self.cols.len(), line.len()),
+            ));
+        }
+        if self.rows.len() == 32 {
+            return Err(ParseError::line(line_no, text, "pattern is taller than 32"));
+        }
+        let mut new_row = 0;
+        for (i, c) in text.char_indices() {
+            let bit = match c {
+                '#' => 1,
+                '.' => 0,
+                _ => {
+                    let found = &text[i..i + c.len_utf8()];
+                    return Err(ParseError::at(line_no, text, found, "expected '.' or '#'"));
+                }
+            };
+            new_row = new_row * 2 + bit;
+            self.cols[i] = self.cols[i] * 2 + bit;
+        }
+        self.rows.push(new_row);
+        Ok(())
+    }
+
+    fn find_mirror(&self, with_fix: bool) -> (Option<usize>, Option<usize>) {
+        let mut result = (None, None);
+        for mirror in 1..self.rows.len() {
+            let compare_len = mirror.min(self.rows.len() - mirror);
+            let mut diff = Vec::new();
+            for i in 0..compare_len {
+                let v1 = self.rows[mirror - i - 1];
+                let v2 = self.rows[mirror + i];
+                if v1 != v2  {
+                    diff.push((v1, v2));
+                }
+            }
+            if with_fix {
+                if diff.len() == 1 {
+                    let (mut v1, mut v2) = diff[0];
+                    let mut diff_bits = 0;
+                    while diff_bits < 2 && (v1 > 0 || v2 > 0) {
+                        if v1 % 2 != v2 % 2 {
+                            diff_bits += 1;
+                        }
+                        v1 /= 2;
+                        v2 /= 2;
+                    }
+                    if diff_bits == 1 {
+                        result.0.replace(mirror);
+                        break;
+                    }
+                }
+            } else if diff.is_empty() {
+                result.0.replace(mirror);
+                break;
+            }
+        }
+
+        for mirror in 1..self.cols.len() {
+            let compare_len = mirror.min(self.cols.len() - mirror);
+            let mut diff = Vec::new();
+            for i in 0..compare_len {
+                let v1 = self.cols[mirror - i - 1];
+                let v2 = self.cols[mirror + i];
+                if v1 != v2  {
+                    diff.push((v1, v2));
+                }
+            }
+            if with_fix {
+                if diff.len() == 1 {
+                    let (mut v1, mut v2) =diff[0];
+                    let mut diff_bits = 0;
+                    while diff_bits < 2 && (v1 > 0 || v2 > 0) {
+                        if v1 % 2 != v2 % 2 {
+                            diff_bits += 1;
+                        }
+                        v1 /= 2;
+                        v2 /= 2;
+                    }
+                    if diff_bits == 1 {
+                        result.1.replace(mirror);
+                        break;
+                    }
+                }
+            } else if diff.is_empty() {
+                result.1.replace(mirror);
+                break;
+            }
+        }
+        result
+    }
+
+}
+
+pub fn read_graphs(input: &str) -> Result<Vec<Graph>, ParseError> {
+    let mut graphs = Vec::<Graph>::new();
+    let mut graph = Graph::new();
+    for (line, text) in lines(input).chain([(0, "")]) {
+        let text = text.trim();
+        if text.is_empty() {
+            if !graph.rows.is_empty() {
+                graphs.push(graph);
+                graph = Graph::new();
+            }
+            continue;
+        }
+        graph.add_line(line, text)?;
+    }
+    Ok(graphs)
+}
+
+// None when a pattern mirrors both across a row and across a column
+fn summarize(graphs: &[Graph], with_fix: bool) -> Option<usize> {
+    let mut sum = 0;
+    for graph in graphs.iter() {
+        let (row_idx, col_idx) = graph.find_mirror(with_fix);
+        if let Some(row_idx) = row_idx {
+            if !with_fix && col_idx.is_some() {
+                warn!("Two mirrors in the graph");
+                return None;
+            }
+            sum += row_idx * 100;
+        } else if let Some(col_idx) = col_idx {
+            sum += col_idx;
+        } else {
+            warn!("No mirror in the graph");
+        }
+    }
+    Some(sum)
+}
+
+pub fn part1(graphs: &[Graph]) -> Option<usize> {
+    summarize(graphs, false)
+}
+
+pub fn part2(graphs: &[Graph]) -> Option<usize> {
+    summarize(graphs, true)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Graph>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_graphs(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
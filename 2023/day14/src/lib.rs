@@ -0,0 +1,180 @@
+use common::{lines, Answer, ParseError, Solution};
+use grid::{Cell, Grid, Pos};
+use std::{collections::HashMap, fmt::Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+impl Cell for Rock {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'O' => Some(Self::Round),
+            '#' => Some(Self::Cube),
+            '.' => Some(Self::Empty),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Round => 'O',
+            Self::Cube => '#',
+            Self::Empty => '.',
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Game {
+    circle: usize,
+    grid: Grid<Rock>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    
+    pub fn new() -> Self {
+        Self { circle: 0, grid: Grid::default() }
+    }
+
+    // `line` is only used to report errors
+    pub fn add_row(&mut self, line: usize, text: &str) -> Result<(), ParseError> {
+        self.grid.parse_line(line, text)?;
+        Ok(())
+    }
+
+    pub fn calculate_part1(&self) -> usize {
+        let mut game = self.clone();
+        game.tilt_north();
+        game.calculate_load()
+    }
+
+    fn calculate_load(&self) -> usize {
+        let mut load = 0;
+        for (pos, r) in self.grid.iter() {
+            if *r == Rock::Round {
+                load += self.grid.rows() - pos.row;
+            }
+        }
+        load
+    }
+
+    fn tilt_north(&mut self) {
+        for col in 0..self.grid.cols() {
+            let mut free = 0;
+            for row in 0..self.grid.rows() {
+                match self.grid[Pos::new(row, col)] {
+                    Rock::Cube => free = row + 1,
+                    Rock::Round => {
+                        self.grid[Pos::new(row, col)] = Rock::Empty;
+                        self.grid[Pos::new(free, col)] = Rock::Round;
+                        free += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    // tilt to the north, then turn the west side up for the next tilt
+    fn rotate_90(&self) -> Self {
+        let mut game = self.clone();
+        game.tilt_north();
+        game.grid = game.grid.rotate_clockwise();
+        game
+    } 
+
+    fn rotate_360(&self) -> Self {
+        let mut game = self.rotate_90().rotate_90().rotate_90().rotate_90();
+        game.circle += 1;
+        game
+    }
+
+}
+
+
+impl Display for Game {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+    
+}
+
+pub fn read_game(input: &str) -> Result<Game, ParseError> {
+    let mut game = Game::new();
+    for (line, text) in lines(input) {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        game.add_row(line, text)?;
+    }
+    Ok(game)
+}
+
+pub fn part2(game: &Game) -> usize {
+    let mut history = HashMap::<usize, Vec<Game>>::new();
+    let mut next_game = None;
+    let load: usize = game.calculate_load();
+    next_game.replace(game.rotate_360());
+    history.insert(load, vec![game.clone()]);
+
+    let end_loops = 1000000000;
+    while next_game.as_ref().unwrap().circle < end_loops {
+        let mut game = next_game.take().unwrap();
+        let load = game.calculate_load();
+        let mut repeats = 0;
+        if history.contains_key(&load) {
+            for pre_game in history.get(&load).unwrap().iter() {
+                if game.grid == pre_game.grid {
+                    repeats = game.circle - pre_game.circle;
+                }
+            }
+        }
+        if repeats > 0 {
+            game.circle += repeats * ((end_loops - game.circle) / repeats);
+            next_game.replace(game);
+            break;
+        }
+        next_game.replace(game.rotate_360());
+        if let std::collections::hash_map::Entry::Vacant(e) = history.entry(load) {
+            e.insert(vec![game]);
+        } else {
+            history.get_mut(&load).unwrap().push(game);
+        }
+    }
+
+    let mut game = next_game.take().unwrap();
+    while game.circle < end_loops {
+        game = game.rotate_360();
+    }
+    game.calculate_load()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_game(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.calculate_part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
@@ -0,0 +1,162 @@
+use common::{lines, Answer, ParseError, Solution};
+use std::collections::LinkedList;
+
+pub struct LensSlot {
+    label: String,
+    count: usize,
+}
+
+impl LensSlot {
+    // a label followed by `-` or `=` and a focal length 1-9, `step` is a
+    // slice of `line_text`
+    pub fn new(line: usize, line_text: &str, step: &str) -> Result<Self, ParseError> {
+        if step.is_empty() {
+            return Err(ParseError::at(line, line_text, step, "expected a step"));
+        }
+        let Some(op) = step.find(['=', '-']) else {
+            return Err(ParseError::at(line, line_text, step, "expected '=' or '-'"));
+        };
+        let (label, rest) = step.split_at(op);
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(ParseError::at(line, line_text, label, "expected a lowercase label"));
+        }
+        let count = match rest.as_bytes() {
+            [b'-'] => 0,
+            [b'=', d @ b'1'..=b'9'] => (d - b'0') as usize,
+            _ => {
+                return Err(ParseError::at(
+                    line,
+                    line_text,
+                    rest,
+                    "expected '-' or '=' with a focal length 1-9",
+                ))
+            }
+        };
+        Ok(Self {
+            label: label.to_string(),
+            count,
+        })
+    }
+
+    fn hash(&self) -> usize {
+        let mut hash = 0;
+        for c in self.label.as_bytes() {
+            hash = (hash + (*c) as u32) * 17 % 256
+        }
+        hash as usize
+    }
+}
+
+pub struct Game {
+    boxes: [LinkedList<LensSlot>; 256]
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+
+    pub fn new() -> Self {
+        let boxes = [(); 256].map(|_| LinkedList::new());
+        Self { boxes }
+    }
+
+    pub fn slot_ops(&mut self, lens_slot: LensSlot) {
+        let b_idx = lens_slot.hash();
+        let b = self.boxes.get_mut(b_idx).unwrap();
+        let mut s_idx = None;
+        for (slot, idx) in b.iter_mut().zip(0..) {
+            if slot.label == lens_slot.label {
+                s_idx.replace(idx);
+                break;
+            }
+        }
+        if let Some(s_idx) = s_idx {
+            let mut splited = b.split_off(s_idx);
+            let _ = splited.pop_front().unwrap();
+            if lens_slot.count > 0 {
+                b.push_back(lens_slot);
+            }
+            b.append(&mut splited);
+        } else {
+            if lens_slot.count > 0 {
+                b.push_back(lens_slot);
+            }
+        }
+    }
+
+    pub fn sum(&self) -> usize {
+        let mut sum = 0; 
+        for (b, b_score) in self.boxes.iter().zip(1..) {
+            for (s, s_score) in b.iter().zip(1..) {
+                sum += b_score * s_score * s.count;
+            }
+        }
+        sum
+    }
+    
+}
+
+// the steps of each line, with the line they are on
+fn steps(input: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+    lines(input)
+        .map(|(line, text)| (line, text.trim_end()))
+        .filter(|(_, text)| !text.is_empty())
+        .flat_map(|(line, text)| text.split(',').map(move |step| (line, text, step)))
+}
+
+// every step has to be a valid `LensSlot`, and there has to be one
+pub fn validate_steps(input: &str) -> Result<(), ParseError> {
+    let mut found = false;
+    for (line, text, step) in steps(input) {
+        LensSlot::new(line, text, step)?;
+        found = true;
+    }
+    if !found {
+        return Err(ParseError::eof(lines(input).count().max(1), "expected the steps"));
+    }
+    Ok(())
+}
+
+pub fn part1(text: &str) -> u32 {
+    let mut part1 = 0;
+    for len in text.trim_end().split(',') {
+        let mut hash = 0;
+        for c in len.as_bytes() {
+            hash = (hash + (*c) as u32) * 17 % 256
+        }
+        part1 += hash;
+    }
+    part1
+}
+
+pub fn part2(text: &str) -> Result<usize, ParseError> {
+    let mut game = Game::new();
+    for (line, line_text, step) in steps(text) {
+        game.slot_ops(LensSlot::new(line, line_text, step)?);
+    }
+    Ok(game.sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        validate_steps(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        // the steps were validated when parsing
+        part2(input).map_or(Answer::Unsolved, Answer::from)
+    }
+}
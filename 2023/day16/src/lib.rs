@@ -0,0 +1,167 @@
+use common::{lines, par_map, Answer, ParseError, Solution};
+use grid::{Cell, Direction, Grid, Picture, Pos, Rgb};
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mirror {
+    // .
+    Empty,
+    // -
+    Horizantal,
+    // |
+    Vertical,
+    // /
+    Slash,
+    // \
+    BackSlash,
+
+}
+
+impl Cell for Mirror {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '|' => Some(Self::Vertical),
+            '-' => Some(Self::Horizantal),
+            '/' => Some(Self::Slash),
+            '\\' => Some(Self::BackSlash),
+            '.' => Some(Self::Empty),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::Horizantal => '-',
+            Self::Vertical => '|',
+            Self::Slash => '/',
+            Self::BackSlash => '\\',
+        }
+    }
+}
+
+pub struct Game {
+    map: Grid<Mirror>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self { map: Grid::default() }
+    }
+
+    // `line` is only used to report errors
+    pub fn add_input(&mut self, line: usize, input: &str) -> Result<(), ParseError> {
+        self.map.parse_line(line, input)?;
+        Ok(())
+    }
+
+    fn calculation(&self, init_status: (Pos, Direction)) -> usize {
+        self.energized(init_status).len()
+    }
+
+    fn energized(&self, init_status: (Pos, Direction)) -> HashSet<Pos> {
+        let mut reached: HashSet<Pos> = HashSet::new();
+        let mut reached_status: HashSet<(Pos, Direction)> = HashSet::new();
+        let mut bfs = VecDeque::new();
+        bfs.push_back(init_status);
+        while let Some(state) = bfs.pop_front() {
+            if reached_status.contains(&state) {
+                continue;
+            }
+            let (pos, direction) = state;
+            let mirror = self.map[pos];
+            reached_status.insert(state);
+            reached.insert(pos);
+            let directions = match (mirror, direction.is_vertical()) {
+                // a slash turns north into east and west into south
+                (Mirror::Slash, true) => vec![direction.turn_right()],
+                (Mirror::Slash, false) => vec![direction.turn_left()],
+                (Mirror::BackSlash, true) => vec![direction.turn_left()],
+                (Mirror::BackSlash, false) => vec![direction.turn_right()],
+                (Mirror::Horizantal, true) | (Mirror::Vertical, false) => {
+                    vec![direction.turn_left(), direction.turn_right()]
+                }
+                _ => vec![direction],
+            };
+            let next_status = directions
+                .into_iter()
+                .filter_map(|d| self.map.step(pos, d).map(|next| (next, d)));
+            bfs.extend(next_status)
+        }
+
+        reached
+    }
+
+    pub fn part1(&self) -> usize {
+        self.calculation((Pos::new(0, 0), Direction::East))
+    }
+
+    // the tiles energized by the beam of part 1, with the mirrors on top
+    pub fn render(&self) -> Picture {
+        let energized = self.energized((Pos::new(0, 0), Direction::East));
+        Picture::from_grid(&self.map, |pos, mirror| match (mirror, energized.contains(&pos)) {
+            (Mirror::Empty, true) => Rgb::YELLOW,
+            (Mirror::Empty, false) => Rgb::DARK,
+            (_, true) => Rgb::RED,
+            (_, false) => Rgb::GREY,
+        })
+    }
+
+    pub fn part2(&self) -> usize {
+        let rows = self.map.rows();
+        let cols = self.map.cols();
+        let mut edges = Vec::with_capacity(2 * (rows + cols));
+        for row in 0..rows {
+            edges.push((Pos::new(row, 0), Direction::East));
+            edges.push((Pos::new(row, cols - 1), Direction::West));
+        }
+        for col in 0..cols {
+            edges.push((Pos::new(0, col), Direction::South));
+            edges.push((Pos::new(rows - 1, col), Direction::North));
+        }
+        // every edge tile is its own beam
+        par_map(&edges, |&init| self.calculation(init))
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub fn read_game(input: &str) -> Result<Game, ParseError> {
+    let mut game = Game::new();
+    for (line, text) in lines(input) {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        game.add_input(line, text)?;
+    }
+    if game.map.rows() == 0 {
+        return Err(ParseError::eof(lines(input).count().max(1), "expected the contraption"));
+    }
+    Ok(game)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_game(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
@@ -0,0 +1,379 @@
+use common::{Answer, ParseError, Solution};
+use grid::{Cell, Direction, Grid, Picture, Pos, Rgb};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+// heat loss of one city block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block(u32);
+
+impl Cell for Block {
+    fn from_char(c: char) -> Option<Self> {
+        c.to_digit(10).map(Block)
+    }
+
+    fn to_char(&self) -> char {
+        char::from_digit(self.0, 10).unwrap()
+    }
+}
+
+struct GridStatue {
+    row: usize,
+    col: usize,
+    direction: Direction,
+    steps: usize,
+    distance: u32,
+}
+
+struct DistanceMap<'a> {
+    distances: &'a Grid<Block>,
+    grids: Grid<HashMap<Direction, [Option<u32>; 10]>>,
+    size: (usize, usize),
+    min_steps: usize,
+    max_steps: usize,
+}
+
+impl<'a> DistanceMap<'a> {
+    fn new(map: &'a Grid<Block>, min_steps: usize, max_steps: usize) -> Self {
+        Self {
+            distances: map,
+            grids: Grid::new(map.rows(), map.cols(), HashMap::new()),
+            size: (map.rows(), map.cols()),
+            min_steps,
+            max_steps,
+        }
+    }
+
+    fn goto(&mut self, row: usize, col: usize, pre: &GridStatue) -> Option<GridStatue> {
+        let (direction, steps, delta) = if row == pre.row {
+            if col < pre.col {
+                let delta: u32 = self.distances.row(row)[col..pre.col]
+                    .iter()
+                    .fold(0, |crr, v| crr + v.0);
+                match pre.direction {
+                    Direction::West => Some((Direction::West, pre.steps + pre.col - col, delta)),
+                    Direction::East => None,
+                    _ => Some((Direction::West, pre.col - col, delta)),
+                }
+            } else {
+                let delta = self.distances.row(row)[pre.col + 1..col + 1]
+                    .iter()
+                    .fold(0, |crr, v| crr + v.0);
+                match pre.direction {
+                    Direction::East => Some((Direction::East, pre.steps + col - pre.col, delta)),
+                    Direction::West => None,
+                    _ => Some((Direction::East, col - pre.col, delta)),
+                }
+            }
+        } else if col == pre.col {
+            if row < pre.row {
+                let delta: u32 = (row..pre.row)
+                    .fold(0, |crr, r| crr + self.distances[Pos::new(r, col)].0);
+                match pre.direction {
+                    Direction::North => Some((Direction::North, pre.steps + pre.row - row, delta)),
+                    Direction::South => None,
+                    _ => Some((Direction::North, pre.row - row, delta)),
+                }
+            } else {
+                let delta: u32 = (pre.row + 1..row + 1)
+                    .fold(0, |crr, r| crr + self.distances[Pos::new(r, col)].0);
+                match pre.direction {
+                    Direction::South => {
+                        Some((Direction::South, pre.steps + row - pre.row, delta))
+                    }
+                    Direction::North => None,
+                    _ => Some((Direction::South, row - pre.row, delta)),
+                }
+            }
+        } else {
+            unreachable!()
+        }?;
+        if steps > self.max_steps || steps < self.min_steps {
+            return None;
+        }
+        let grid_status = &mut self.grids[Pos::new(row, col)];
+        grid_status.entry(direction).or_insert([None; 10]);
+        let distance = pre.distance + delta;
+        let distances = grid_status.get_mut(&direction).unwrap();
+        if distances[steps - 1].is_none_or(|v| v > distance) {
+            distances[steps - 1].replace(distance);
+        } else {
+            return None;
+        }
+        Some(GridStatue {
+            row,
+            col,
+            direction,
+            steps,
+            distance,
+        })
+    }
+
+    fn get_grid_distance_min(
+        &self,
+        row: usize,
+        col: usize,
+        direction: Direction,
+        steps: usize,
+    ) -> Option<u32> {
+        let grid_status = &self.grids[Pos::new(row, col)];
+        grid_status.get(&direction)?[self.min_steps - 1..steps.min(self.max_steps)]
+            .iter()
+            .filter_map(|v| *v)
+            .min()
+    }
+}
+
+pub struct Game {
+    map: Grid<Block>,
+}
+
+impl Game {
+    pub fn new(text: &str) -> Result<Self, ParseError> {
+        let map = Grid::parse(text)?;
+        if map.rows() == 0 {
+            return Err(ParseError::eof(1, "the map is empty"));
+        }
+        Ok(Self { map })
+    }
+
+    // None when the crucible can't reach the factory
+    pub fn part1(&self) -> Option<u32> {
+        let mut distance_map = DistanceMap::new(&self.map, 1, 3);
+        let mut bfs: VecDeque<GridStatue> = VecDeque::new();
+        let init_status = GridStatue {
+            row: 0,
+            col: 0,
+            direction: Direction::North,
+            steps: 1,
+            distance: 0,
+        };
+        bfs.push_back(init_status);
+        let init_status = GridStatue {
+            row: 0,
+            col: 0,
+            direction: Direction::West,
+            steps: 1,
+            distance: 0,
+        };
+        bfs.push_back(init_status);
+
+        while let Some(status) = bfs.pop_front() {
+            if let Some(min_distance) = distance_map.get_grid_distance_min(
+                status.row,
+                status.col,
+                status.direction,
+                status.steps,
+            ) {
+                if min_distance < status.distance {
+                    continue;
+                }
+            }
+            if status.row > 0 {
+                if let Some(new_status) = distance_map.goto(status.row - 1, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.row + 1 < distance_map.size.0 {
+                if let Some(new_status) = distance_map.goto(status.row + 1, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col > 0 {
+                if let Some(new_status) = distance_map.goto(status.row, status.col - 1, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col + 1 < distance_map.size.1 {
+                if let Some(new_status) = distance_map.goto(status.row, status.col + 1, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+        }
+        let row = distance_map.size.0 - 1;
+        let col = distance_map.size.1 - 1;
+        [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .iter()
+        .filter_map(|direction| distance_map.get_grid_distance_min(row, col, *direction, 3))
+        .min()
+    }
+
+    pub fn part2(&self) -> Option<u32> {
+        let mut distance_map = DistanceMap::new(&self.map, 4, 10);
+        let mut bfs: VecDeque<GridStatue> = VecDeque::new();
+        let init_status = GridStatue {
+            row: 0,
+            col: 0,
+            direction: Direction::North,
+            steps: 4,
+            distance: 0,
+        };
+        bfs.push_back(init_status);
+        let init_status = GridStatue {
+            row: 0,
+            col: 0,
+            direction: Direction::West,
+            steps: 4,
+            distance: 0,
+        };
+        bfs.push_back(init_status);
+
+        while let Some(status) = bfs.pop_front() {
+            if let Some(min_distance) = distance_map.get_grid_distance_min(
+                status.row,
+                status.col,
+                status.direction,
+                status.steps,
+            ) {
+                if min_distance < status.distance {
+                    continue;
+                }
+            }
+
+            if status.row > 0 && status.direction == Direction::North {
+                if let Some(new_status) = distance_map.goto(status.row - 1, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.row > 3 && status.direction != Direction::North {
+                if let Some(new_status) = distance_map.goto(status.row - 4, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.row + 1 < distance_map.size.0 && status.direction == Direction::South {
+                if let Some(new_status) = distance_map.goto(status.row + 1, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.row + 4 < distance_map.size.0 && status.direction != Direction::South {
+                if let Some(new_status) = distance_map.goto(status.row + 4, status.col, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col > 0 && status.direction == Direction::West {
+                if let Some(new_status) = distance_map.goto(status.row, status.col - 1, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col > 3 && status.direction != Direction::West {
+                if let Some(new_status) = distance_map.goto(status.row, status.col - 4, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col + 1 < distance_map.size.1 && status.direction == Direction::East {
+                if let Some(new_status) = distance_map.goto(status.row, status.col + 1, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+            if status.col + 4 < distance_map.size.1 && status.direction != Direction::East {
+                if let Some(new_status) = distance_map.goto(status.row, status.col + 4, &status) {
+                    bfs.push_back(new_status);
+                }
+            }
+        }
+
+        let row = distance_map.size.0 - 1;
+        let col = distance_map.size.1 - 1;
+        [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .iter()
+        .filter_map(|direction| distance_map.get_grid_distance_min(row, col, *direction, 10))
+        .min()
+    }
+}
+
+impl Game {
+    // a plain dijkstra that remembers where every state came from, only used
+    // to draw the path, the parts keep their own search
+    fn best_path(&self, min_steps: usize, max_steps: usize) -> Option<Vec<Pos>> {
+        // (pos, direction, blocks moved in that direction), None before the first move
+        type State = (Pos, Option<Direction>, usize);
+        let start: State = (Pos::new(0, 0), None, 0);
+        let end = Pos::new(self.map.rows() - 1, self.map.cols() - 1);
+        let mut distances: HashMap<State, u32> = HashMap::from([(start, 0)]);
+        let mut parents: HashMap<State, State> = HashMap::new();
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+        let mut last = None;
+        while let Some(Reverse((distance, state))) = heap.pop() {
+            if distances[&state] < distance {
+                continue;
+            }
+            let (pos, direction, steps) = state;
+            if pos == end && steps >= min_steps {
+                last = Some(state);
+                break;
+            }
+            for next_direction in Direction::ALL {
+                let next_steps = match direction {
+                    None => 1,
+                    Some(d) if d == next_direction => steps + 1,
+                    Some(d) if d == next_direction.opposite() || steps < min_steps => continue,
+                    Some(_) => 1,
+                };
+                if next_steps > max_steps {
+                    continue;
+                }
+                let Some(next_pos) = self.map.step(pos, next_direction) else {
+                    continue;
+                };
+                let next: State = (next_pos, Some(next_direction), next_steps);
+                let next_distance = distance + self.map[next_pos].0;
+                if distances.get(&next).is_none_or(|d| *d > next_distance) {
+                    distances.insert(next, next_distance);
+                    parents.insert(next, state);
+                    heap.push(Reverse((next_distance, next)));
+                }
+            }
+        }
+        let mut path = Vec::new();
+        let mut state = last?;
+        path.push(state.0);
+        while let Some(parent) = parents.get(&state) {
+            state = *parent;
+            path.push(state.0);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // the heat loss of every block, with the best path of part 1 on top when there is one
+    pub fn render(&self) -> Picture {
+        let mut picture = Picture::from_grid(&self.map, |_, block| {
+            let heat = (block.0 * 25) as u8;
+            Rgb(heat, heat / 3, 40)
+        });
+        if let Some(path) = self.best_path(1, 3) {
+            picture.path(&path, Rgb::GREEN);
+        }
+        picture
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Game;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Game::new(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
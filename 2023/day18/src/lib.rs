@@ -0,0 +1,322 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+use grid::{Picture, Pos, Rgb};
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod oracle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    start: (usize, usize),
+    direction: Direction,
+    len: usize,
+}
+
+struct Map {
+    segments: Vec<Segment>,
+}
+
+impl Map {
+    fn new(digs: Vec<(Direction, usize)>) -> Self {
+        let mut segments: Vec<((i32, i32), Direction, usize)> = Vec::new();
+        let mut minial_row = 0;
+        let mut minial_col = 0;
+        let mut start = (0, 0);
+        let mut cut = false;
+        for (direction, len) in digs {
+            let (next, next_cut) = match direction {
+                Direction::Up => ((start.0 - len as i32, start.1), false),
+                Direction::Down => ((start.0 + len as i32, start.1), false),
+                Direction::Left => ((start.0, start.1 - len as i32), true),
+                Direction::Right => ((start.0, start.1 + len as i32), true),
+            };
+            if cut && !next_cut {
+                let last = segments.last_mut().unwrap();
+                last.2 += 1;
+                segments.push(match direction {
+                    Direction::Up => ((start.0 - 1, start.1), Direction::Up, len - 1),
+                    Direction::Down => ((start.0 + 1, start.1), Direction::Down, len - 1),
+                    _ => unreachable!(),
+                })
+            } else {
+                segments.push((start, direction, len));
+            }
+            start = next;
+            cut = next_cut;
+            if minial_row > start.0 {
+                minial_row = start.0;
+            }
+            if minial_col > start.1 {
+                minial_col = start.1;
+            }
+        }
+        assert_eq!(start, (0, 0));
+        if cut {
+            let first = segments.first_mut().unwrap();
+            let extend_last = match first.1 {
+                Direction::Up => {
+                    *first = ((first.0 .0 - 1, first.0 .1), Direction::Up, first.2 - 1);
+                    true
+                }
+                Direction::Down => {
+                    *first = ((first.0 .0 + 1, first.0 .1), Direction::Down, first.2 - 1);
+                    true
+                }
+                _ => false,
+            };
+            if extend_last {
+                let last = segments.last_mut().unwrap();
+                last.2 += 1;
+            }
+        }
+
+        let mut segments: Vec<Segment> = segments
+            .into_iter()
+            .map(|(start, direction, len)| Segment {
+                start: (
+                    (start.0 - minial_row) as usize,
+                    (start.1 - minial_col) as usize,
+                ),
+                direction,
+                len,
+            })
+            .collect();
+
+        segments.sort_by_key(|a| a.start.1);
+
+        Self { segments }
+    }
+
+    fn calculate_area(&mut self) -> usize {
+        let mut sum = self.segments.iter().fold(0, |cur, seg| cur + seg.len);
+        let mut rows: Vec<usize> = self
+            .segments
+            .iter()
+            .filter_map(|s| match s.direction {
+                Direction::Up | Direction::Down => None,
+                Direction::Left | Direction::Right => Some(s.start.0),
+            })
+            .collect::<HashSet<usize>>()
+            .into_iter()
+            .collect();
+        rows.sort();
+
+        for (&row_start, &row_end) in rows.iter().zip(rows[1..].iter()) {
+            let filter = self.segments.iter().filter(|seg| match seg.direction {
+                Direction::Up => seg.start.0 >= row_start && seg.start.0 - seg.len <= row_start,
+                Direction::Down => seg.start.0 <= row_start + 1 && seg.start.0 + seg.len > row_start,
+                Direction::Left => false,
+                Direction::Right => false,
+            });
+            let mut square_area = Vec::new();
+            let mut col_start = None;
+            for seg in filter {
+                if let Some(col_start) = col_start.take() {
+                    square_area.push((col_start, seg.start.1 - col_start));
+                } else {
+                    col_start.replace(seg.start.1 + 1);
+                }
+            }
+            assert!(col_start.is_none());
+
+            sum = square_area
+                .iter()
+                .fold(sum, |cur, (_, len)| cur + len * (row_end - row_start - 1));
+
+            let filter = self.segments.iter().filter(|seg| match seg.direction {
+                Direction::Up => seg.start.0 >= row_start && seg.start.0 - seg.len < row_start,
+                Direction::Down => seg.start.0 <= row_start && seg.start.0 + seg.len > row_start,
+                Direction::Left | Direction::Right => seg.start.0 == row_start,
+            });
+            let mut row_area = Vec::new();
+            let mut col_start = None;
+            let mut sq_idx = 0;
+            for seg in filter {
+                if sq_idx >= square_area.len() {
+                    break;
+                }
+                if let Some(col_start) = col_start.take() {
+                    let len = match seg.direction {
+                        Direction::Up | Direction::Down | Direction::Right => {
+                            seg.start.1 - col_start
+                        }
+                        Direction::Left => seg.start.1 + 1 - seg.len - col_start,
+                    };
+                    if len > 0 {
+                        while sq_idx < square_area.len() {
+                            let (s, l) = square_area[sq_idx];
+                            if s > col_start {
+                                assert!(s >= col_start + len);
+                                break;
+                            } else if s + l > col_start {
+                                assert!(s + l >= col_start + len);
+                                row_area.push((col_start, len));
+                                break;
+                            }
+                            sq_idx += 1;
+                        }
+                    }
+                }
+                col_start.replace(match seg.direction {
+                    Direction::Up | Direction::Down | Direction::Left => seg.start.1 + 1,
+                    Direction::Right => seg.start.1 + seg.len,
+                });
+            }
+            sum = row_area.iter().fold(sum, |cur, (_, len)| cur + len);
+        }
+
+        sum
+    }
+}
+
+// the lagoon outline has to end where it started
+fn is_closed(digs: &[(Direction, usize)]) -> bool {
+    let mut pos = (0i64, 0i64);
+    for &(direction, len) in digs {
+        let len = len as i64;
+        match direction {
+            Direction::Up => pos.0 -= len,
+            Direction::Down => pos.0 += len,
+            Direction::Left => pos.1 -= len,
+            Direction::Right => pos.1 += len,
+        }
+    }
+    pos == (0, 0)
+}
+
+pub struct DigPlan {
+    digs_part1: Vec<(Direction, usize)>,
+    digs_part2: Vec<(Direction, usize)>,
+}
+
+impl DigPlan {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let mut digs_part1 = Vec::new();
+        let mut digs_part2 = Vec::new();
+        let mut last_line = 0;
+        for (line, text) in lines(input) {
+            last_line = line;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut line_reader = text.split_ascii_whitespace();
+            let (Some(direction), Some(len), Some(hex), None) = (
+                line_reader.next(),
+                line_reader.next(),
+                line_reader.next(),
+                line_reader.next(),
+            ) else {
+                return Err(ParseError::line(line, text, "expected a direction, a length and a colour"));
+            };
+            let len: usize = parse_number(line, text, len)?;
+            let dig = match direction {
+                "U" => (Direction::Up, len),
+                "D" => (Direction::Down, len),
+                "L" => (Direction::Left, len),
+                "R" => (Direction::Right, len),
+                _ => return Err(ParseError::at(line, text, direction, "expected U, D, L or R")),
+            };
+            digs_part1.push(dig);
+            let invalid_hex = || ParseError::at(line, text, hex, "expected a colour like (#70c710)");
+            if hex.len() != 9 || !hex.starts_with("(#") || !hex.ends_with(')') {
+                return Err(invalid_hex());
+            }
+            let len = usize::from_str_radix(&hex[2..7], 16).map_err(|_| invalid_hex())?;
+            let dig = match &hex[7..8] {
+                "0" => (Direction::Right, len),
+                "1" => (Direction::Down, len),
+                "2" => (Direction::Left, len),
+                "3" => (Direction::Up, len),
+                _ => return Err(invalid_hex()),
+            };
+            digs_part2.push(dig);
+        }
+        if digs_part1.is_empty() {
+            return Err(ParseError::eof(last_line + 1, "the dig plan is empty"));
+        }
+        for digs in [&digs_part1, &digs_part2] {
+            if !is_closed(digs) {
+                return Err(ParseError::eof(last_line + 1, "the dig plan does not return to its start"));
+            }
+        }
+        Ok(Self {
+            digs_part1,
+            digs_part2,
+        })
+    }
+
+    pub fn part1(&self) -> usize {
+        let mut map = Map::new(self.digs_part1.clone());
+        map.calculate_area()
+    }
+
+    // the lagoon of part 1, the trench and the polygon through its middle
+    pub fn render(&self) -> Picture {
+        let mut corners = vec![(0i64, 0i64)];
+        for &(direction, len) in self.digs_part1.iter() {
+            let (row, col) = *corners.last().unwrap();
+            let len = len as i64;
+            corners.push(match direction {
+                Direction::Up => (row - len, col),
+                Direction::Down => (row + len, col),
+                Direction::Left => (row, col - len),
+                Direction::Right => (row, col + len),
+            });
+        }
+        let min_row = corners.iter().map(|c| c.0).min().unwrap();
+        let min_col = corners.iter().map(|c| c.1).min().unwrap();
+        let corners: Vec<Pos> = corners
+            .into_iter()
+            .map(|(row, col)| Pos::new((row - min_row) as usize, (col - min_col) as usize))
+            .collect();
+        let rows = corners.iter().map(|c| c.row).max().unwrap() + 1;
+        let cols = corners.iter().map(|c| c.col).max().unwrap() + 1;
+        let mut picture = Picture::new(rows, cols, Rgb::DARK);
+        for pair in corners.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            for row in from.row.min(to.row)..=from.row.max(to.row) {
+                for col in from.col.min(to.col)..=from.col.max(to.col) {
+                    picture.fill(Pos::new(row, col), Rgb::GREY);
+                }
+            }
+        }
+        let polygon: Vec<(f64, f64)> = corners
+            .iter()
+            .map(|c| (c.row as f64 + 0.5, c.col as f64 + 0.5))
+            .collect();
+        picture.polygon(&polygon, Rgb::BLUE);
+        picture.path(&corners, Rgb::RED);
+        picture
+    }
+
+    pub fn part2(&self) -> usize {
+        let mut map = Map::new(self.digs_part2.clone());
+        map.calculate_area()
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = DigPlan;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        DigPlan::new(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
@@ -0,0 +1,235 @@
+// random dig plans, and a flood fill of the dug out cells to check the
+// scanline of `Map::calculate_area`
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use common::Rng;
+
+use crate::DigPlan;
+
+// a connected blob of lattice cells with its holes filled in
+fn blob(rng: &mut Rng, rows: usize, cols: usize) -> Vec<Vec<bool>> {
+    // random cells, keeping only the ones connected to the first
+    let random: Vec<Vec<bool>> = (0..rows)
+        .map(|_| (0..cols).map(|_| !rng.one_in(3)).collect())
+        .collect();
+    let mut cells = vec![vec![false; cols]; rows];
+    let start = (rng.below(rows), rng.below(cols));
+    cells[start.0][start.1] = true;
+    let mut bfs = VecDeque::from([start]);
+    while let Some((r, c)) = bfs.pop_front() {
+        let nbs = [(r + 1, c), (r, c + 1), (r.wrapping_sub(1), c), (r, c.wrapping_sub(1))];
+        for (nr, nc) in nbs {
+            if nr < rows && nc < cols && random[nr][nc] && !cells[nr][nc] {
+                cells[nr][nc] = true;
+                bfs.push_back((nr, nc));
+            }
+        }
+    }
+    // anything the outside can't reach is a hole
+    let mut outside = vec![vec![false; cols + 2]; rows + 2];
+    let mut bfs = VecDeque::from([(0_usize, 0_usize)]);
+    outside[0][0] = true;
+    while let Some((r, c)) = bfs.pop_front() {
+        let nbs = [(r + 1, c), (r, c + 1), (r.wrapping_sub(1), c), (r, c.wrapping_sub(1))];
+        for (nr, nc) in nbs {
+            if nr >= rows + 2 || nc >= cols + 2 || outside[nr][nc] {
+                continue;
+            }
+            if nr >= 1 && nc >= 1 && nr <= rows && nc <= cols && cells[nr - 1][nc - 1] {
+                continue;
+            }
+            outside[nr][nc] = true;
+            bfs.push_back((nr, nc));
+        }
+    }
+    for r in 0..rows {
+        for c in 0..cols {
+            cells[r][c] = !outside[r + 1][c + 1];
+        }
+    }
+    cells
+}
+
+// two cells touching only by a corner would make the outline cross itself
+fn has_pinch(cells: &[Vec<bool>]) -> bool {
+    for r in 1..cells.len() {
+        for c in 1..cells[0].len() {
+            let (a, b) = (cells[r - 1][c - 1], cells[r - 1][c]);
+            let (d, e) = (cells[r][c - 1], cells[r][c]);
+            if (a && e && !b && !d) || (b && d && !a && !e) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// the outline as unit steps between lattice corners, clockwise
+fn outline(cells: &[Vec<bool>]) -> Vec<((usize, usize), char)> {
+    let mut edges = HashSet::new();
+    for r in 0..cells.len() {
+        for c in 0..cells[0].len() {
+            if !cells[r][c] {
+                continue;
+            }
+            let square = [
+                ((r, c), (r, c + 1)),
+                ((r, c + 1), (r + 1, c + 1)),
+                ((r + 1, c + 1), (r + 1, c)),
+                ((r + 1, c), (r, c)),
+            ];
+            for (from, to) in square {
+                // a side shared by two cells is inside the blob
+                if !edges.remove(&(to, from)) {
+                    edges.insert((from, to));
+                }
+            }
+        }
+    }
+    let next: HashMap<(usize, usize), (usize, usize)> = edges.into_iter().collect();
+    let start = *next.keys().min().unwrap();
+    let mut steps = Vec::new();
+    let mut pos = start;
+    loop {
+        let to = next[&pos];
+        let direction = match (to.0 as isize - pos.0 as isize, to.1 as isize - pos.1 as isize) {
+            (0, 1) => 'R',
+            (1, 0) => 'D',
+            (0, -1) => 'L',
+            _ => 'U',
+        };
+        steps.push((pos, direction));
+        pos = to;
+        if pos == start {
+            break;
+        }
+    }
+    steps
+}
+
+fn stretch(rng: &mut Rng, len: usize) -> Vec<usize> {
+    let mut coords = vec![0];
+    for _ in 0..len {
+        coords.push(coords.last().unwrap() + rng.range(1, 5) as usize);
+    }
+    coords
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    let (rows, cols) = (rng.range(1, 9) as usize, rng.range(1, 9) as usize);
+    let mut cells = blob(rng, rows, cols);
+    while has_pinch(&cells) {
+        cells = blob(rng, rows, cols);
+    }
+    let mut steps = outline(&cells);
+    if rng.one_in(2) {
+        // walk the other way round
+        steps = steps
+            .iter()
+            .rev()
+            .map(|&(pos, d)| {
+                let (pos, d) = match d {
+                    'R' => ((pos.0, pos.1 + 1), 'L'),
+                    'D' => ((pos.0 + 1, pos.1), 'U'),
+                    'L' => ((pos.0, pos.1 - 1), 'R'),
+                    _ => ((pos.0 - 1, pos.1), 'D'),
+                };
+                (pos, d)
+            })
+            .collect();
+    }
+    // start from a random corner
+    let corners: Vec<usize> = (0..steps.len())
+        .filter(|&i| steps[i].1 != steps[(i + steps.len() - 1) % steps.len()].1)
+        .collect();
+    let first = *rng.pick(&corners);
+    steps.rotate_left(first);
+
+    let row_coords = stretch(rng, rows);
+    let col_coords = stretch(rng, cols);
+    let mut digs: Vec<(char, usize)> = Vec::new();
+    for ((r, c), d) in steps {
+        let len = match d {
+            'R' => col_coords[c + 1] - col_coords[c],
+            'D' => row_coords[r + 1] - row_coords[r],
+            'L' => col_coords[c] - col_coords[c - 1],
+            _ => row_coords[r] - row_coords[r - 1],
+        };
+        match digs.last_mut() {
+            Some((last, total)) if *last == d => *total += len,
+            _ => digs.push((d, len)),
+        }
+    }
+    let mut text = String::new();
+    for (d, len) in digs {
+        let code = match d {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            _ => 3,
+        };
+        text.push_str(&format!("{} {} (#{:05x}{})\n", d, len, len, code));
+    }
+    text
+}
+
+// digs the trench cell by cell and floods the ground around it, on a grid
+// of doubled resolution so the flood also gets between two trenches that
+// run side by side
+pub fn brute_force(text: &str) -> usize {
+    let mut pos = (0_i64, 0_i64);
+    let mut trench = HashSet::from([pos]);
+    for line in text.lines() {
+        let mut f = line.split_whitespace();
+        let d = f.next().unwrap();
+        let len: i64 = f.next().unwrap().parse().unwrap();
+        for _ in 0..len * 2 {
+            match d {
+                "R" => pos.1 += 1,
+                "D" => pos.0 += 1,
+                "L" => pos.1 -= 1,
+                _ => pos.0 -= 1,
+            }
+            trench.insert(pos);
+        }
+    }
+    let min_r = trench.iter().map(|p| p.0).min().unwrap() - 2;
+    let max_r = trench.iter().map(|p| p.0).max().unwrap() + 2;
+    let min_c = trench.iter().map(|p| p.1).min().unwrap() - 2;
+    let max_c = trench.iter().map(|p| p.1).max().unwrap() + 2;
+    let mut outside = HashSet::from([(min_r, min_c)]);
+    let mut bfs = VecDeque::from([(min_r, min_c)]);
+    while let Some((r, c)) = bfs.pop_front() {
+        for next in [(r + 1, c), (r - 1, c), (r, c + 1), (r, c - 1)] {
+            if next.0 < min_r || next.0 > max_r || next.1 < min_c || next.1 > max_c {
+                continue;
+            }
+            if trench.contains(&next) || !outside.insert(next) {
+                continue;
+            }
+            bfs.push_back(next);
+        }
+    }
+    // only the even points are real cells
+    let mut area = 0;
+    for r in (min_r..=max_r).step_by(2) {
+        for c in (min_c..=max_c).step_by(2) {
+            if !outside.contains(&(r, c)) {
+                area += 1;
+            }
+        }
+    }
+    area
+}
+
+#[test]
+fn test_area() {
+    for case in 0..2000 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let plan = DigPlan::new(&text).expect("Invalid generated plan");
+        let area = brute_force(&text);
+        assert_eq!(plan.part1(), area, "case {}:\n{}", case, text);
+        assert_eq!(plan.part2(), area, "case {}:\n{}", case, text);
+    }
+}
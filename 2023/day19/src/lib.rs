@@ -0,0 +1,531 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone)]
+enum Prop {
+    X(usize),
+    M(usize),
+    A(usize),
+    S(usize),
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Less(Prop),
+    Large(Prop),
+}
+
+impl Condition {
+    fn check(&self, prop: &Prop) -> Option<bool> {
+        match self {
+            Self::Less(cond) => match (cond, prop) {
+                (Prop::X(threshold), Prop::X(v)) => Some(*v < *threshold),
+                (Prop::M(threshold), Prop::M(v)) => Some(*v < *threshold),
+                (Prop::A(threshold), Prop::A(v)) => Some(*v < *threshold),
+                (Prop::S(threshold), Prop::S(v)) => Some(*v < *threshold),
+                _ => None,
+            },
+            Self::Large(cond) => match (cond, prop) {
+                (Prop::X(threshold), Prop::X(v)) => Some(*v > *threshold),
+                (Prop::M(threshold), Prop::M(v)) => Some(*v > *threshold),
+                (Prop::A(threshold), Prop::A(v)) => Some(*v > *threshold),
+                (Prop::S(threshold), Prop::S(v)) => Some(*v > *threshold),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Behavior {
+    Accept,
+    Reject,
+    Jump(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Part {
+    props: Vec<Prop>,
+    score: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PartRange {
+    x: (usize, usize),
+    m: (usize, usize),
+    a: (usize, usize),
+    s: (usize, usize),
+}
+
+struct Workflow {
+    id: usize,
+    conditions: Vec<Condition>,
+    behaviors: Vec<Behavior>,
+}
+
+struct MachineBuilder {
+    workflows: HashMap<String, Workflow>,
+    names: HashMap<String, usize>,
+    // jumps to workflows that are not defined yet, with where they were first seen
+    undefined: HashMap<String, ParseError>,
+}
+
+struct Machine {
+    workflows: Vec<Workflow>,
+}
+
+impl MachineBuilder {
+    fn new() -> Self {
+        let mut names = HashMap::new();
+        names.insert("in".to_string(), 0);
+        Self {
+            workflows: HashMap::new(),
+            names,
+            undefined: HashMap::new(),
+        }
+    }
+
+    fn get_id(&mut self, name: &str) -> usize {
+        let len = self.names.len();
+        *self.names.entry(name.to_string()).or_insert(len)
+    }
+
+    fn get_behavior(&mut self, line: usize, text: &str, name: &str) -> Result<Behavior, ParseError> {
+        Ok(match name {
+            "A" => Behavior::Accept,
+            "R" => Behavior::Reject,
+            _ => {
+                if !is_workflow_name(name) {
+                    return Err(ParseError::at(line, text, name, "expected A, R or a workflow name"));
+                }
+                if !self.workflows.contains_key(name) && !self.undefined.contains_key(name) {
+                    self.undefined.insert(
+                        name.to_string(),
+                        ParseError::at(line, text, name, "undefined workflow"),
+                    );
+                }
+                Behavior::Jump(self.get_id(name))
+            }
+        })
+    }
+
+    // `line` is only used to report errors
+    fn add_new_workflow(&mut self, line: usize, text: &str) -> Result<(), ParseError> {
+        let Some((name, rules)) = text
+            .strip_suffix('}')
+            .and_then(|text| text.split_once('{'))
+        else {
+            return Err(ParseError::line(line, text, "expected a workflow like name{rules}"));
+        };
+        if !is_workflow_name(name) {
+            return Err(ParseError::at(line, text, name, "expected a workflow name"));
+        }
+        if self.workflows.contains_key(name) {
+            return Err(ParseError::at(line, text, name, "duplicate workflow"));
+        }
+        self.undefined.remove(name);
+        let id = self.get_id(name);
+        let mut conditions = Vec::new();
+        let mut behaviors = Vec::new();
+        let mut rules = rules.split(',').peekable();
+        while let Some(c_str) = rules.next() {
+            if rules.peek().is_none() {
+                // the last rule is the fallback
+                behaviors.push(self.get_behavior(line, text, c_str)?);
+                break;
+            }
+            let Some((cond, behavior_name)) = c_str.split_once(':') else {
+                return Err(ParseError::at(line, text, c_str, "expected a rule like a<2006:qkq"));
+            };
+            let Some(op) = cond.find(['>', '<']) else {
+                return Err(ParseError::at(line, text, cond, "expected '<' or '>'"));
+            };
+            let prop_name = &cond[..op];
+            let threshold: usize = parse_number(line, text, &cond[op + 1..])?;
+            let prop = match prop_name {
+                "x" => Prop::X(threshold),
+                "m" => Prop::M(threshold),
+                "a" => Prop::A(threshold),
+                "s" => Prop::S(threshold),
+                _ => return Err(ParseError::at(line, text, prop_name, "expected x, m, a or s")),
+            };
+            let condition = if &cond[op..op + 1] == ">" {
+                Condition::Large(prop)
+            } else {
+                Condition::Less(prop)
+            };
+            conditions.push(condition);
+            behaviors.push(self.get_behavior(line, text, behavior_name)?);
+        }
+        self.workflows.insert(
+            name.to_string(),
+            Workflow {
+                id,
+                conditions,
+                behaviors,
+            },
+        );
+        Ok(())
+    }
+
+    fn build(self, line: usize) -> Result<Machine, ParseError> {
+        if let Some(e) = self.undefined.into_values().min_by_key(|e| (e.line, e.column)) {
+            return Err(e);
+        }
+        if !self.workflows.contains_key("in") {
+            return Err(ParseError::eof(line, "missing the in workflow"));
+        }
+        let mut workflows = self
+            .workflows.into_values()
+            .collect::<Vec<Workflow>>();
+        workflows.sort_by_key(|a| a.id);
+        Ok(Machine { workflows })
+    }
+}
+
+fn is_workflow_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase())
+}
+
+impl Part {
+    // `line` is only used to report errors
+    fn new(line: usize, text: &str) -> Result<Self, ParseError> {
+        let Some(body) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) else {
+            return Err(ParseError::line(line, text, "expected a part like {x=1,m=2,a=3,s=4}"));
+        };
+        let mut props = Vec::new();
+        let mut score = 0;
+        for p_str in body.split(',') {
+            let Some((p_name, p_v)) = p_str.split_once('=') else {
+                return Err(ParseError::at(line, text, p_str, "expected a rating like x=1"));
+            };
+            let p_v: usize = parse_number(line, text, p_v)?;
+            score += p_v;
+            let prop = match p_name {
+                "x" => Prop::X(p_v),
+                "m" => Prop::M(p_v),
+                "a" => Prop::A(p_v),
+                "s" => Prop::S(p_v),
+                _ => return Err(ParseError::at(line, text, p_name, "expected x, m, a or s")),
+            };
+            props.push(prop);
+        }
+        Ok(Self { props, score })
+    }
+}
+
+impl Workflow {
+    fn handle_part(&self, part: &Part) -> Behavior {
+        for (condition, behavior) in self.conditions.iter().zip(self.behaviors.iter()) {
+            if let Some(m) = part
+                .props
+                .iter()
+                .filter_map(|prop| condition.check(prop))
+                .next()
+            {
+                if m {
+                    return *behavior;
+                }
+            }
+        }
+        *self.behaviors.last().unwrap()
+    }
+
+    fn handle_range(&self, part_range: PartRange) -> Vec<(PartRange, Behavior)> {
+        let mut sub_parts = Vec::new();
+        let mut remain = Some(part_range);
+        for (condition, &behavior) in self.conditions.iter().zip(self.behaviors.iter()) {
+            if let Some(mut part_range) = remain.take() {
+                match condition {
+                    Condition::Less(Prop::X(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.x.0 >= threshold {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.x.1 <= threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.x.1 = threshold;
+                                part_range.x.0 = threshold;
+                                if new_range.x.0 < new_range.x.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.x.0 < part_range.x.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Less(Prop::M(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.m.0 >= threshold {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.m.1 <= threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.m.1 = threshold;
+                                part_range.m.0 = threshold;
+                                if new_range.m.0 < new_range.m.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.m.0 < part_range.m.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Less(Prop::A(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.a.0 >= threshold {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.a.1 <= threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.a.1 = threshold;
+                                part_range.a.0 = threshold;
+                                if new_range.a.0 < new_range.a.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.a.0 < part_range.a.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Less(Prop::S(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.s.0 >= threshold {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.s.1 <= threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.s.1 = threshold;
+                                part_range.s.0 = threshold;
+                                if new_range.s.0 < new_range.s.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.s.0 < part_range.s.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Large(Prop::X(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.x.1 <= threshold + 1 {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.x.0 > threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.x.0 = threshold + 1;
+                                part_range.x.1 = threshold + 1;
+                                if new_range.x.0 < new_range.x.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.x.0 < part_range.x.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Large(Prop::M(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.m.1 <= threshold + 1 {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.m.0 > threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.m.0 = threshold + 1;
+                                part_range.m.1 = threshold + 1;
+                                if new_range.m.0 < new_range.m.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.m.0 < part_range.m.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Large(Prop::A(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.a.1 <= threshold + 1 {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.a.0 > threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.a.0 = threshold + 1;
+                                part_range.a.1 = threshold + 1;
+                                if new_range.a.0 < new_range.a.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.a.0 < part_range.a.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                    Condition::Large(Prop::S(threshold)) => {
+                        let threshold = *threshold;
+                        if part_range.s.1 <= threshold + 1 {
+                            remain.replace(part_range);
+                        } else {
+                            if part_range.s.0 > threshold {
+                                sub_parts.push((part_range, behavior));
+                            } else {
+                                let mut new_range = part_range;
+                                new_range.s.0 = threshold + 1;
+                                part_range.s.1 = threshold + 1;
+                                if new_range.s.0 < new_range.s.1 {
+                                    sub_parts.push((new_range, behavior));
+                                }
+                                if part_range.s.0 < part_range.s.1 {
+                                    remain.replace(part_range);
+                                }
+                            }
+                        }
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+        if let Some(part_range) = remain.take() {
+            sub_parts.push((part_range, *self.behaviors.last().unwrap()));
+        }
+        sub_parts
+    }
+}
+
+impl Machine {
+    fn handle_part(&self, part: &Part) -> bool {
+        let mut w_idx = Some(0);
+        while w_idx.is_some() {
+            let w = &self.workflows[w_idx.unwrap()];
+            match w.handle_part(part) {
+                Behavior::Accept => {
+                    return true;
+                }
+                Behavior::Reject => {
+                    return false;
+                }
+                Behavior::Jump(idx) => {
+                    w_idx.replace(idx);
+                }
+            }
+        }
+        false
+    }
+
+    fn handle_range(&self, part_range: PartRange) -> Vec<PartRange> {
+        let mut bfs = VecDeque::new();
+        bfs.push_back((part_range, 0));
+        let mut accept_ranges = Vec::new();
+        while let Some((range, w_idx)) = bfs.pop_front() {
+            let workflow = &self.workflows[w_idx];
+            for (sub_range, behavior) in workflow.handle_range(range).into_iter() {
+                match behavior {
+                    Behavior::Accept => {
+                        accept_ranges.push(sub_range);
+                    },
+                    Behavior::Reject => {}
+                    Behavior::Jump(idx) => {
+                        bfs.push_back((sub_range, idx));
+                    }
+                }
+            }
+        }
+        accept_ranges
+    }
+}
+
+impl PartRange {
+
+    fn count(&self) -> usize {
+        (self.x.1-self.x.0)  * (self.m.1-self.m.0) * (self.a.1-self.a.0) * (self.s.1-self.s.0) 
+    }
+    
+}
+
+pub struct System {
+    machine: Machine,
+    parts: Vec<Part>,
+}
+
+impl System {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let mut lines = lines(input);
+        let mut builder = MachineBuilder::new();
+        let mut last_line = 0;
+        for (line, text) in lines.by_ref() {
+            last_line = line;
+            let l = text.trim();
+            if l.is_empty() {
+                break;
+            }
+            builder.add_new_workflow(line, l)?;
+        }
+        let machine = builder.build(last_line + 1)?;
+
+        let mut parts = Vec::new();
+        for (line, text) in lines {
+            let l = text.trim();
+            if l.is_empty() {
+                break;
+            }
+            parts.push(Part::new(line, l)?);
+        }
+        Ok(Self { machine, parts })
+    }
+
+    pub fn part1(&self) -> usize {
+        let mut part1 = 0;
+        for part in self.parts.iter() {
+            if self.machine.handle_part(part) {
+                part1 += part.score;
+            }
+        }
+        part1
+    }
+
+    pub fn part2(&self) -> usize {
+        let part_range = PartRange {
+            x: (1, 4001),
+            m: (1, 4001),
+            a: (1, 4001),
+            s: (1, 4001),
+        };
+        let accept_ranges = self.machine.handle_range(part_range);
+        accept_ranges.iter().fold(0, |crr, r| crr + r.count())
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = System;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        System::new(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
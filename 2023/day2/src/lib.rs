@@ -0,0 +1,265 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+// the colours of the puzzle, other inputs may bring their own
+pub const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+// how many cubes of each colour, in one draw or in a bag
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(BTreeMap<String, u32>);
+
+pub type Draw = Cubes;
+pub type Bag = Cubes;
+
+impl Cubes {
+    // the bag of part 1
+    pub fn part1() -> Self {
+        COLOURS.iter().zip([12, 13, 14]).map(|(c, n)| (*c, n)).collect()
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.0.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.0.iter().map(|(c, n)| (c.as_str(), *n))
+    }
+
+    fn add(&mut self, colour: &str, count: u32) {
+        *self.0.entry(colour.to_string()).or_default() += count;
+    }
+
+    // the colours in both, as many as the larger has
+    pub fn union(&self, other: &Cubes) -> Cubes {
+        let mut union = self.clone();
+        for (colour, count) in other.iter() {
+            let n = union.0.entry(colour.to_string()).or_default();
+            *n = (*n).max(count);
+        }
+        union
+    }
+
+    // the colours missing from a bag count as none of them
+    pub fn fits_in(&self, bag: &Bag) -> bool {
+        self.iter().all(|(colour, count)| count <= bag.get(colour))
+    }
+
+    // the product of the counts, colours that never showed up count as 1
+    pub fn power(&self) -> u64 {
+        self.0.values().map(|n| *n as u64).product()
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Cubes {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(iter: I) -> Self {
+        let mut cubes = Cubes::default();
+        for (colour, count) in iter {
+            cubes.add(colour, count);
+        }
+        cubes
+    }
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes: Vec<String> = self.iter().map(|(c, n)| format!("{} {}", n, c)).collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+// "12 red, 13 green, 14 blue", in any colours
+impl FromStr for Cubes {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cubes(1, s, s, None)
+    }
+}
+
+// "<count> <colour>, ..." found in `line_text`, `colours` limits the colours
+// when given
+fn parse_cubes(
+    line: usize,
+    line_text: &str,
+    text: &str,
+    colours: Option<&[&str]>,
+) -> Result<Cubes, ParseError> {
+    let expected = match colours {
+        Some(colours) => format!("expected \"<count> <{}>\"", colours.join("|")),
+        None => "expected \"<count> <colour>\"".to_string(),
+    };
+    let mut cubes = Cubes::default();
+    for field in text.split(',').map(str::trim) {
+        let mut words = field.split_whitespace();
+        let (Some(count), Some(colour), None) = (words.next(), words.next(), words.next()) else {
+            return Err(ParseError::at(line, line_text, field, expected));
+        };
+        let known = colours.is_none_or(|colours| colours.contains(&colour));
+        if !count.bytes().all(|b| b.is_ascii_digit()) || !known {
+            return Err(ParseError::at(line, line_text, field, expected));
+        }
+        cubes.add(colour, parse_number(line, line_text, count)?);
+    }
+    Ok(cubes)
+}
+
+// a draw that took more cubes of a colour than the bag holds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub game: u32,
+    // 1-based, in the order of the line
+    pub draw: usize,
+    pub colour: String,
+    pub count: u32,
+    pub limit: u32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Game {} draw {}: {} {} but the bag has {}",
+            self.game, self.draw, self.count, self.colour, self.limit
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    // "Game N: <draw>; <draw>; ..."
+    pub fn parse(line: usize, text: &str, colours: Option<&[&str]>) -> Result<Self, ParseError> {
+        let header = || ParseError::line(line, text, "expected \"Game N:\"");
+        let rest = text.strip_prefix("Game").ok_or_else(header)?;
+        let after = rest.trim_start();
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if after.len() == rest.len() || digits == 0 {
+            return Err(header());
+        }
+        let id = parse_number(line, text, &after[..digits])?;
+        let remain = &after[digits..];
+        let draws = remain
+            .strip_prefix(':')
+            .ok_or_else(|| ParseError::at(line, text, remain, "expected ':' after the game number"))?;
+        let draws = draws
+            .split(';')
+            .map(|draw| parse_cubes(line, text, draw, colours))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { id, draws })
+    }
+
+    pub fn is_feasible(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(bag))
+    }
+
+    // the fewest cubes of each colour the game could be played with
+    pub fn minimal_bag(&self) -> Bag {
+        self.draws
+            .iter()
+            .fold(Bag::default(), |bag, draw| bag.union(draw))
+    }
+
+    pub fn violations(&self, bag: &Bag) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (idx, draw) in self.draws.iter().enumerate() {
+            for (colour, count) in draw.iter() {
+                let limit = bag.get(colour);
+                if count > limit {
+                    violations.push(Violation {
+                        game: self.id,
+                        draw: idx + 1,
+                        colour: colour.to_string(),
+                        count,
+                        limit,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+pub fn read_games(input: &str, colours: Option<&[&str]>) -> Result<Vec<Game>, ParseError> {
+    lines(input)
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(line, text)| Game::parse(line, text.trim_end(), colours))
+        .collect()
+}
+
+pub fn feasible_games<'a>(games: &'a [Game], bag: &'a Bag) -> impl Iterator<Item = &'a Game> {
+    games.iter().filter(|game| game.is_feasible(bag))
+}
+
+// the smallest bag every game could have been played with
+pub fn minimal_bag_for_all(games: &[Game]) -> Bag {
+    games
+        .iter()
+        .fold(Bag::default(), |bag, game| bag.union(&game.minimal_bag()))
+}
+
+pub fn part1(games: &[Game], bag: &Bag) -> u32 {
+    feasible_games(games, bag).map(|game| game.id).sum()
+}
+
+pub fn part2(games: &[Game]) -> u64 {
+    games.iter().map(|game| game.minimal_bag().power()).sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_games(input, Some(&COLOURS))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input, &Bag::part1()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{minimal_bag_for_all, part1, part2, read_games, Bag, Game, Violation, COLOURS};
+
+    const EXAMPLE: &str = include_str!("../example_input");
+
+    #[test]
+    fn test_queries() {
+        let games = read_games(EXAMPLE, Some(&COLOURS)).unwrap();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games[0].draws.len(), 3);
+        assert_eq!(part1(&games, &Bag::part1()), 8);
+        assert_eq!(part2(&games), 2286);
+        assert_eq!(games[0].minimal_bag().to_string(), "6 blue, 2 green, 4 red");
+        assert_eq!(minimal_bag_for_all(&games).to_string(), "15 blue, 13 green, 20 red");
+        assert_eq!(
+            games[2].violations(&Bag::part1()),
+            vec![Violation {
+                game: 3,
+                draw: 1,
+                colour: "red".to_string(),
+                count: 20,
+                limit: 12,
+            }]
+        );
+
+        // any colours, when they aren't limited
+        let bag: Bag = "2 red, 1 purple".parse().unwrap();
+        let game = Game::parse(1, "Game 7: 1 purple, 2 red; 3 teal", None).unwrap();
+        assert!(!game.is_feasible(&bag));
+        assert_eq!(game.violations(&bag)[0].to_string(), "Game 7 draw 2: 3 teal but the bag has 0");
+        assert_eq!(game.minimal_bag().power(), 6);
+        assert!(Game::parse(1, "Game 7: 1 purple", Some(&COLOURS)).is_err());
+        assert!("12 red 13".parse::<Bag>().is_err());
+    }
+}
@@ -0,0 +1,409 @@
+use common::{debug, extras, lines, Answer, ParseError, Solution};
+use math::crt;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Broadcaster,
+    FlipFlop(FlipFlop),
+    Conjunction(Conjunction),
+    Test,
+}
+
+#[derive(Debug, Default, Clone)]
+struct NodeConnection {
+    from: Vec<usize>,
+    to: Vec<usize>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct FlipFlop {
+    on: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Conjunction {
+    on: Vec<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine {
+    ids: HashMap<String, usize>,
+    connections: Vec<NodeConnection>,
+    nodes: Vec<Node>,
+}
+
+struct MachineBuilder {
+    nodes: HashMap<String, Node>,
+    connections: HashMap<String, NodeConnection>,
+    ids: HashMap<String, usize>,
+}
+
+impl FlipFlop {
+    fn handle_pulse(&mut self, pulse: Pulse) -> Option<Pulse> {
+        match pulse {
+            Pulse::High => None,
+            Pulse::Low => {
+                self.on = !self.on;
+                if self.on {
+                    Some(Pulse::High)
+                } else {
+                    Some(Pulse::Low)
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.on = false;
+    }
+}
+
+impl Conjunction {
+    fn init(&mut self, from_len: usize) {
+        assert!(self.on.is_empty());
+        self.on = [false].repeat(from_len);
+    }
+
+    fn handle_pulse(&mut self, from_idx: usize, pulse: Pulse) -> Option<Pulse> {
+        match pulse {
+            Pulse::High => {
+                self.on[from_idx] = true;
+            }
+            Pulse::Low => {
+                self.on[from_idx] = false;
+            }
+        }
+        if self.on.iter().all(|v| *v) {
+            Some(Pulse::Low)
+        } else {
+            Some(Pulse::High)
+        }
+    }
+
+    fn clear(&mut self) {
+        self.on = [false].repeat(self.on.len());
+    }
+}
+
+impl MachineBuilder {
+    fn new() -> Self {
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        ids.insert("broadcaster".to_string(), 0);
+        Self {
+            nodes: HashMap::new(),
+            connections: HashMap::new(),
+            ids,
+        }
+    }
+
+    // `line` is only used to report errors
+    fn add_line(&mut self, line_no: usize, line: &str) -> Result<(), ParseError> {
+        let Some((node_name, f)) = line.split_once("->") else {
+            return Err(ParseError::line(line_no, line, "expected a module like %a -> b"));
+        };
+        let node_name = node_name.trim();
+        let f = f.split(',');
+        let (name, node) = if let Some(name) = node_name.strip_prefix("%") {
+            (name, Node::FlipFlop(FlipFlop::default()))
+        } else if let Some(name) = node_name.strip_prefix("&") {
+            (name, Node::Conjunction(Conjunction::default()))
+        } else if node_name == "broadcaster" {
+            (node_name, Node::Broadcaster)
+        } else {
+            return Err(ParseError::at(line_no, line, node_name, "expected %, & or broadcaster"));
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(ParseError::at(line_no, line, node_name, "expected a module name"));
+        }
+        if self.nodes.insert(name.to_string(), node).is_some() {
+            return Err(ParseError::at(line_no, line, name, "duplicate module"));
+        }
+        if !self.ids.contains_key(name) {
+            self.ids.insert(name.to_string(), self.ids.len());
+        }
+        let node_id = *self.ids.get(name).unwrap();
+        if !self.connections.contains_key(name) {
+            self.connections
+                .insert(name.to_string(), NodeConnection::default());
+        }
+        for link_to in f {
+            let link_to = link_to.trim();
+            if link_to.is_empty() || !link_to.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(line_no, line, link_to, "expected a module name"));
+            }
+            if !self.ids.contains_key(link_to) {
+                self.ids.insert(link_to.to_string(), self.ids.len());
+            }
+            let link_to_id = *self.ids.get(link_to).unwrap();
+            if !self.connections.contains_key(link_to) {
+                self.connections
+                    .insert(link_to.to_string(), NodeConnection::default());
+            }
+            self.connections
+                .get_mut(link_to)
+                .unwrap()
+                .from
+                .push(node_id);
+            self.connections.get_mut(name).unwrap().to.push(link_to_id);
+        }
+        Ok(())
+    }
+
+    fn build(mut self, line: usize) -> Result<Machine, ParseError> {
+        if !self.nodes.contains_key("broadcaster") {
+            return Err(ParseError::eof(line, "missing the broadcaster"));
+        }
+        let names: Vec<String> = self
+            .ids.keys().filter_map(|name| {
+                if !self.nodes.contains_key(name) {
+                    Some(name.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        for name in names.into_iter() {
+            self.nodes.insert(name, Node::Test);
+        }
+        let mut nodes = Vec::new();
+        let mut connections = Vec::new();
+        for (name, &idx) in self.ids.iter() {
+            let node = self.nodes.remove(name).unwrap();
+            let connection = self.connections.remove(name).unwrap();
+            let node = match node {
+                Node::Conjunction(mut state) => {
+                    state.init(connection.from.len());
+                    Node::Conjunction(state)
+                }
+                _ => node,
+            };
+            nodes.push((node, idx));
+            connections.push((connection, idx));
+        }
+        nodes.sort_by_key(|a| a.1);
+        connections.sort_by_key(|a| a.1);
+        Ok(Machine {
+            ids: self.ids,
+            nodes: nodes.into_iter().map(|(node, _)| node).collect(),
+            connections: connections
+                .into_iter()
+                .map(|(connection, _)| connection)
+                .collect()
+        })
+    }
+}
+
+impl Machine {
+    // `start` is the broadcaster or a flip-flop it sends to
+    fn press(&mut self, start: usize, stop: Option<usize>, stop_status: Option<&Node>) -> (usize, usize, bool) {
+        let mut low_count: usize = 1;
+        let mut high_count = 0;
+        let mut find_stop_state = false;
+        let mut bfs = VecDeque::new();
+        let pulse = match self.nodes.get_mut(start).unwrap() {
+            Node::Broadcaster => {
+                Some(Pulse::Low)
+            },
+            Node::FlipFlop(state) => {
+                state.handle_pulse(Pulse::Low)
+            },
+            _ => None
+        };
+        if let Some(pulse) = pulse {
+            for &to_id in self.connections.get(start).unwrap().to.iter() {
+                bfs.push_back((start, pulse, to_id));
+            }
+        }
+        while let Some((from_id, pulse, to_id)) = bfs.pop_front() {
+            match pulse {
+                Pulse::High => high_count += 1,
+                Pulse::Low => low_count += 1,
+            }
+            let from_idx: usize = self
+                .connections
+                .get(to_id)
+                .unwrap()
+                .from
+                .iter()
+                .position(|&v| v == from_id)
+                .unwrap();
+            let node = self.nodes.get_mut(to_id).unwrap();
+            let pulse = match node {
+                Node::Broadcaster => Some(pulse),
+                Node::Conjunction(state) => state.handle_pulse(from_idx, pulse),
+                Node::FlipFlop(state) => state.handle_pulse(pulse),
+                Node::Test => None,
+            };
+            if let Some(stop) = stop {
+                if stop == to_id {
+                    // cut pulse when reached to stop
+                    if *node == *stop_status.unwrap() {
+                        find_stop_state = true;
+                    }
+                    continue;
+                }
+            }
+            if let Some(pulse) = pulse {
+                for &next_id in self.connections.get(to_id).unwrap().to.iter() {
+                    bfs.push_back((to_id, pulse, next_id))
+                }
+            }
+        }
+        (low_count, high_count, find_stop_state)
+    }
+
+    fn reset(&mut self) {
+        for node in self.nodes.iter_mut() {
+            match node {
+                Node::Conjunction(state) => state.clear(),
+                Node::FlipFlop(state) => state.clear(),
+                _ => {}
+            }
+        }
+    }
+
+}
+
+impl Machine {
+    fn calcuate_repeats(&mut self, start: usize, stop: usize, stop_status: Node) -> Option<(usize, usize)> {
+        self.reset();
+        let mut snapshots: Vec<(Vec<Node>, usize)> = Vec::new();
+        let mut press = 0;
+        while press < 100000 {
+            let (_, _, find) = self.press(start, Some(stop), Some(&stop_status));
+            press += 1;
+            if find {
+                let snapshot = self.nodes.clone();
+                for (pre_snapshot, pre_press) in snapshots.iter() {
+                    if *pre_snapshot == snapshot {
+                        let pre_press = *pre_press;
+                        let repeat = press - pre_press;
+                        return Some((pre_press, repeat));
+                    }
+                }
+                snapshots.push((snapshot, press));
+            }
+        }
+        None
+    }
+}
+
+impl Machine {
+    // the conjunctions that feed the one in front of rx, each with the
+    // broadcaster output whose counter drives it. None when the machine isn't
+    // built like that, as in the examples
+    fn counters(&self) -> Option<Vec<(usize, usize)>> {
+        let rx = *self.ids.get("rx")?;
+        let &[last] = self.connections[rx].from.as_slice() else {
+            return None;
+        };
+        let stops = &self.connections[last].from;
+        let starts = &self.connections[self.ids["broadcaster"]].to;
+        if stops.len() != starts.len() || !matches!(self.nodes[last], Node::Conjunction(_)) {
+            return None;
+        }
+        let mut counters = Vec::with_capacity(starts.len());
+        for &start in starts {
+            if !matches!(self.nodes[start], Node::FlipFlop(_)) {
+                return None;
+            }
+            // the stops the counter reaches without going through another
+            let mut seen = vec![false; self.nodes.len()];
+            let mut reached = Vec::new();
+            let mut bfs = VecDeque::from([start]);
+            while let Some(id) = bfs.pop_front() {
+                if std::mem::replace(&mut seen[id], true) {
+                    continue;
+                }
+                if stops.contains(&id) {
+                    reached.push(id);
+                    continue;
+                }
+                bfs.extend(self.connections[id].to.iter().copied());
+            }
+            match reached.as_slice() {
+                &[stop] if matches!(self.nodes[stop], Node::Conjunction(_)) => counters.push((start, stop)),
+                _ => return None,
+            }
+        }
+        let mut paired: Vec<usize> = counters.iter().map(|(_, stop)| *stop).collect();
+        paired.sort_unstable();
+        paired.dedup();
+        (paired.len() == stops.len()).then_some(counters)
+    }
+
+    pub fn part1(&self) -> usize {
+        let mut machine = self.clone();
+        let mut part1_counts = (0, 0);
+        let broadcaster = self.ids["broadcaster"];
+        for _ in 0..1000 {
+            let counts = machine.press(broadcaster, None, None);
+            part1_counts.0 += counts.0;
+            part1_counts.1 += counts.1;
+        }
+        part1_counts.0 * part1_counts.1
+    }
+
+    // Part2, input special algorithm: rx gets a low pulse when the counters
+    // behind the conjunctions that feed it all fire on the same press. None
+    // for machines without them
+    pub fn part2(&self) -> Option<usize> {
+        let mut machine = self.clone();
+        let mut repeats = Vec::new();
+        for (start, stop) in self.counters()? {
+            // the stop fires when every input it has was last low
+            let mut state = Conjunction::default();
+            state.init(self.connections[stop].from.len());
+            repeats.push(machine.calcuate_repeats(start, stop, Node::Conjunction(state))?);
+        }
+
+        // (4013, 4013), (4021, 4021), (3881, 3881), (3889, 3889) for the input
+        debug!("Repeats {:?}", repeats);
+
+        // each conjunction first fires after `offset` presses, then every `repeat`
+        extras::record("cycle_lengths", repeats.iter().map(|(_, repeat)| *repeat));
+        let first = repeats.iter().map(|(offset, _)| *offset as u64).max()?;
+        let (press, period) = crt(repeats.iter().map(|&(offset, repeat)| (offset as u64, repeat as u64)))?;
+        let press = press + first.saturating_sub(press).div_ceil(period) * period;
+        Some(press as usize)
+    }
+}
+
+pub fn read_machine(input: &str) -> Result<Machine, ParseError> {
+    let mut builder = MachineBuilder::new();
+    let mut last_line = 0;
+    for (line, text) in lines(input) {
+        last_line = line;
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        builder.add_line(line, text)?;
+    }
+    builder.build(last_line + 1)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Machine;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_machine(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
@@ -0,0 +1,184 @@
+use common::{debug, extras, lines, Answer, ParseError, Solution};
+use grid::{Direction, Grid, Picture, Pos, Rgb};
+use std::collections::HashSet;
+
+pub struct Map {
+    grids: Grid<bool>,
+    start: Pos,
+}
+
+#[derive(Debug, Default)]
+struct MapBuilder {
+    grids: Grid<bool>,
+    start: Option<Pos>,
+}
+
+impl MapBuilder {
+    // `line_no` is only used to report errors
+    fn add_line(&mut self, line_no: usize, line: &str) -> Result<(), ParseError> {
+        let mut row = Vec::with_capacity(line.len());
+        for (idx, c) in line.char_indices() {
+            let g = match c {
+                '.' => true,
+                '#' => false,
+                'S' => {
+                    let start = Pos::new(self.grids.rows(), row.len());
+                    if self.start.replace(start).is_some() {
+                        return Err(ParseError::at(line_no, line, &line[idx..idx + 1], "second start"));
+                    }
+                    true
+                }
+                _ => {
+                    let found = &line[idx..idx + c.len_utf8()];
+                    return Err(ParseError::at(line_no, line, found, "expected '.', '#' or 'S'"));
+                }
+            };
+            row.push(g);
+        }
+        self.grids.push_row(line_no, row)?;
+        Ok(())
+    }
+
+    fn build(self, line: usize) -> Result<Map, ParseError> {
+        let Some(start) = self.start else {
+            return Err(ParseError::eof(line, "missing the start S"));
+        };
+        Ok(Map {
+            grids: self.grids,
+            start,
+        })
+    }
+}
+
+impl Map {
+    fn get_neighbours(&self, point: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.grids.neighbours4(point).filter(|next| self.grids[*next])
+    }
+
+    fn get_neighbours_i64(&self, point: &(i64, i64)) -> Vec<(i64, i64)> {
+        let mut neighbours = Vec::with_capacity(4);
+        for direction in Direction::ALL {
+            let (dr, dc) = direction.offset();
+            let next = (point.0 + dr as i64, point.1 + dc as i64);
+            if *self.grids.get_wrapped(next.0, next.1) {
+                neighbours.push(next);
+            }
+        }
+        neighbours
+    }
+}
+
+fn bfs(map: &Map, steps: usize) -> usize {
+    reachable(map, steps).len()
+}
+
+fn reachable(map: &Map, steps: usize) -> HashSet<Pos> {
+    let mut points = HashSet::new();
+    points.insert(map.start);
+    for _ in 0..steps {
+        let mut next_points = HashSet::new();
+        for point in points.iter() {
+            next_points.extend(map.get_neighbours(*point));
+        }
+        points = next_points;
+    }
+    points
+}
+
+fn infinit_bfs(map: &Map, steps: usize) -> usize {
+    let mut points = HashSet::new();
+    points.insert((map.start.row as i64, map.start.col as i64));
+    for _ in 0..steps {
+        let mut next_points = HashSet::new();
+        for point in points.iter() {
+            next_points.extend(map.get_neighbours_i64(point));
+        }
+        points = next_points;
+    }
+    points.len()
+}
+
+pub fn read_map(input: &str) -> Result<Map, ParseError> {
+    let mut builder = MapBuilder::default();
+    let mut last_line = 0;
+    for (line, text) in lines(input) {
+        last_line = line;
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        builder.add_line(line, text)?;
+    }
+    builder.build(last_line + 1)
+}
+
+pub fn part1(map: &Map) -> usize {
+    bfs(map, 64)
+}
+
+// the plots reachable in exactly 64 steps for part 1
+pub fn render(map: &Map) -> Picture {
+    let points = reachable(map, 64);
+    let mut picture = Picture::from_grid(&map.grids, |pos, plot| match (plot, points.contains(&pos)) {
+        (false, _) => Rgb::GREY,
+        (true, true) => Rgb::GREEN,
+        (true, false) => Rgb::DARK,
+    });
+    picture.fill(map.start, Rgb::RED);
+    picture
+}
+
+pub fn part2(map: &Map) -> usize {
+    debug!("Start {:?}", map.start); // (65, 65)
+    let size = map.grids.rows();
+    debug!("Size {:?}", (size, map.grids.cols())); // (131, 131)
+    let steps = 26501365;
+    let s_repeats = steps / size; // 202300
+    let s_remains = steps % size; // 65
+    
+    // 34920, 96829, 189644, 313365 ...
+    //    61909, 92815, 123721 ...
+    //       30906, 30906 ...
+    let mut values = Vec::new();    
+    for i in 1..4 {
+        let s = s_remains + size * i;
+        let count = infinit_bfs(map, s);
+        debug!("{} repeats: {}", i, count);
+        values.push(count);
+    }
+    let delta1 = values[1] - values[0];
+    let delta2 = values[2] - values[1];
+    let delta_inc = delta2 - delta1;
+
+    // the count after n more repeats is a*n^2 + b*n + c, with n = 1 for the
+    // first value
+    let half = |v: i128| -> Answer {
+        if v % 2 == 0 {
+            (v / 2).into()
+        } else {
+            (v as f64 / 2.0).into()
+        }
+    };
+    let (v0, d1, d2) = (values[0] as i128, delta1 as i128, delta_inc as i128);
+    extras::record("quadratic", [half(d2), half(2 * d1 - 3 * d2), (v0 - d1 + d2).into()]);
+    
+    values[0] + (delta1 + delta1 + delta_inc * (s_repeats - 2)) * (s_repeats - 1) / 2
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_map(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
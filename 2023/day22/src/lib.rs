@@ -0,0 +1,227 @@
+use common::{debug, lines, par_map, parse_number, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Coord {
+    x: usize,
+    y: usize,
+    z: usize,
+}
+
+
+#[derive(Debug, Default)]
+struct Brick {
+    origin: Coord,
+    size: Coord,
+}
+
+
+#[derive(Default)]
+struct SnapshotBuilder {
+    bricks: Vec<Brick>,
+    space: Coord,
+}
+
+
+pub struct Snapshot {
+    bricks: Vec<Brick>,
+    pub space: Coord,
+}
+
+struct Connetions {
+    supports: HashMap<usize, HashSet<usize>>,
+    relies: HashMap<usize, HashSet<usize>>,
+}
+
+
+impl SnapshotBuilder {
+
+    // `line_no` is only used to report errors
+    fn add_brick(&mut self, line_no: usize, line: &str) -> Result<(), ParseError> {
+        let Some((start, end)) = line.split_once('~') else {
+            return Err(ParseError::line(line_no, line, "expected a brick like 1,0,1~1,2,1"));
+        };
+        let coord = |text: &str| -> Result<Coord, ParseError> {
+            let f: Vec<&str> = text.split(',').collect();
+            if f.len() != 3 {
+                return Err(ParseError::at(line_no, line, text, "expected three coordinates"));
+            }
+            Ok(Coord {
+                x: parse_number(line_no, line, f[0])?,
+                y: parse_number(line_no, line, f[1])?,
+                z: parse_number(line_no, line, f[2])?,
+            })
+        };
+        let start_text = start;
+        let (start, end) = (coord(start)?, coord(end)?);
+        if end.x < start.x || end.y < start.y || end.z < start.z {
+            return Err(ParseError::at(line_no, line, start_text, "the brick starts after its end"));
+        }
+        if end.x >= self.space.x {
+            self.space.x = end.x + 1
+        }
+        if end.y >= self.space.y {
+            self.space.y = end.y + 1
+        }
+        if end.z >= self.space.z {
+            self.space.z = end.z + 1
+        }
+        let size = Coord {
+            x: end.x - start.x + 1,
+            y: end.y - start.y + 1,
+            z: end.z - start.z + 1,
+        };
+        self.bricks.push(Brick { origin: start, size });
+        Ok(())
+    }
+    
+    fn build(mut self) -> Snapshot {
+        self.bricks.sort_by_key(|a| a.origin.z);
+        Snapshot {
+            bricks: self.bricks,
+            space: self.space,
+        }
+    }
+}
+
+impl Snapshot {
+    
+    fn simulate(&self) -> Connetions {
+        let mut supports: HashMap<usize, HashSet<usize>> = HashMap::with_capacity(self.bricks.len());
+        let mut relies: HashMap<usize, HashSet<usize>> = HashMap::with_capacity(self.bricks.len());
+        for idx in 0..self.bricks.len() {
+            supports.insert(idx, HashSet::new());
+            relies.insert(idx, HashSet::new());
+        }
+
+        let mut xy_plane_height: Vec<Vec<usize>> = Vec::new();
+        let mut xy_plane_bricks: Vec<Vec<Option<usize>>> = Vec::new();
+        for _ in 0..self.space.x {
+            xy_plane_height.push([0].repeat(self.space.y));
+            xy_plane_bricks.push([None].repeat(self.space.y));
+        }
+        for idx in 0..self.bricks.len() {
+            let brick = self.bricks.get(idx).unwrap();
+            let mut buttom = 0;
+            for x in brick.origin.x..(brick.origin.x + brick.size.x) {
+                for y in brick.origin.y..(brick.origin.y + brick.size.y) {
+                    if xy_plane_height[x][y] > buttom {
+                        buttom = xy_plane_height[x][y];
+                    }
+                }
+            }
+            let mut s_idxes = HashSet::new();
+            for x in brick.origin.x..(brick.origin.x + brick.size.x) {
+                for y in brick.origin.y..(brick.origin.y + brick.size.y) {
+                    if xy_plane_height[x][y] == buttom {
+                        if let Some(s_idx) = xy_plane_bricks[x][y] {
+                            s_idxes.insert(s_idx);
+                        }
+                    }
+                    xy_plane_height[x][y] = buttom + brick.size.z;
+                    xy_plane_bricks[x][y] = Some(idx);
+                }
+            }
+            for s_idx in s_idxes.iter() {
+                if !supports.contains_key(s_idx) {
+                    supports.insert(*s_idx, HashSet::new());
+                }
+                supports.get_mut(s_idx).unwrap().insert(idx);
+            }
+            relies.insert(idx, s_idxes);
+        }
+
+        Connetions {
+            supports,
+            relies
+        }
+    }
+
+}
+
+
+impl Connetions {
+    fn calculate_disintegrate_bricks(&self) -> Vec<usize> {
+        let mut disintegrate_bricks = Vec::new();
+        for (&s_idx, s) in self.supports.iter() {
+            let mut can_disintegrate = true;
+            for rely_idx in s.iter() {
+                if self.relies.get(rely_idx).unwrap().len() == 1 {
+                    can_disintegrate = false;
+                    break;
+                }
+            }
+            if can_disintegrate {
+                disintegrate_bricks.push(s_idx);
+            }
+        }
+        disintegrate_bricks
+    }
+
+    fn calculate_fall_bricks(&self, dis_brick: &usize) -> usize {
+        let mut removed_bricks = HashSet::new();
+        removed_bricks.insert(*dis_brick);
+        let mut bfs = VecDeque::new();
+        bfs.push_back(*dis_brick);
+        while let Some(s_idx) = bfs.pop_front() {
+            for &rely_idx in self.supports.get(&s_idx).unwrap().iter() {
+                if removed_bricks.contains(&rely_idx) {
+                    continue;
+                }
+                if removed_bricks.is_superset(self.relies.get(&rely_idx).unwrap()) {
+                    removed_bricks.insert(rely_idx);
+                    bfs.push_back(rely_idx);
+                }
+            }
+        }
+        removed_bricks.len() - 1
+    }
+}
+
+impl Snapshot {
+    pub fn part1(&self) -> usize {
+        let connection = self.simulate();
+        connection.calculate_disintegrate_bricks().len()
+    }
+
+    pub fn part2(&self) -> usize {
+        let connection = self.simulate();
+        let bricks: Vec<usize> = (0..self.bricks.len()).collect();
+        par_map(&bricks, |idx| connection.calculate_fall_bricks(idx))
+            .into_iter()
+            .sum()
+    }
+}
+
+pub fn read_snapshot(input: &str) -> Result<Snapshot, ParseError> {
+    let mut builder = SnapshotBuilder::default();
+    for (line, text) in lines(input) {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        builder.add_brick(line, text)?;
+    }
+    let snapshot = builder.build();
+    debug!("Space {:?}", snapshot.space);
+    Ok(snapshot)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Snapshot;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_snapshot(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
@@ -0,0 +1,317 @@
+use common::{Answer, ParseError, Solution};
+use grid::{Cell, Direction, Grid, Picture, Pos, Rgb};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Forest,
+    Path,
+    Slope(Direction),
+}
+
+impl Cell for Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(Self::Forest),
+            '.' => Some(Self::Path),
+            '^' => Some(Self::Slope(Direction::North)),
+            '>' => Some(Self::Slope(Direction::East)),
+            'v' => Some(Self::Slope(Direction::South)),
+            '<' => Some(Self::Slope(Direction::West)),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Forest => '#',
+            Self::Path => '.',
+            Self::Slope(Direction::North) => '^',
+            Self::Slope(Direction::East) => '>',
+            Self::Slope(Direction::South) => 'v',
+            Self::Slope(Direction::West) => '<',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Graph {
+    connections: HashMap<usize, HashMap<usize, usize>>,
+    start_id: usize,
+    end_id: usize,
+}
+
+struct Map {
+    ids: HashMap<Pos, usize>,
+    grids: Grid<Tile>,
+}
+
+impl Map {
+    fn from(input: &str, with_slope: bool) -> Result<Self, ParseError> {
+        let mut grids: Grid<Tile> = Grid::parse(input)?;
+        if !with_slope {
+            grids = grids.map(|&tile| match tile {
+                Tile::Slope(_) => Tile::Path,
+                _ => tile,
+            });
+        }
+        let mut ids = HashMap::new();
+        for (pos, tile) in grids.iter() {
+            if *tile != Tile::Forest {
+                ids.insert(pos, ids.len());
+            }
+        }
+        Ok(Self { ids, grids })
+    }
+
+    fn build_graph(&self) -> Graph {
+        // build connections
+        let mut connections: Vec<HashMap<usize, usize>> = Vec::new();
+        let mut rev_connections: Vec<HashMap<usize, usize>> = Vec::new();
+        for _ in 0..self.ids.len() {
+            connections.push(HashMap::with_capacity(4));
+            rev_connections.push(HashMap::with_capacity(4));
+        }
+        for (&pos, &id) in self.ids.iter() {
+            let tile = self.grids[pos];
+            for direction in Direction::ALL {
+                // slopes can only be walked downhill
+                if tile != Tile::Path && tile != Tile::Slope(direction) {
+                    continue;
+                }
+                let Some(nb) = self.grids.step(pos, direction) else {
+                    continue;
+                };
+                if let Some(&nb_id) = self.ids.get(&nb) {
+                    connections[id].insert(nb_id, 1);
+                    rev_connections[nb_id].insert(id, 1);
+                }
+            }
+        }
+        let start_id = 0;
+        let end_id = connections.len() - 1;
+
+        connections.last_mut().unwrap().clear(); // end not allowed as node
+        rev_connections.first_mut().unwrap().clear(); // start not allowed as node
+        for i in 0..connections.len() {
+            connections[i].remove(&start_id);
+            rev_connections[i].remove(&end_id);
+        }
+
+        let mut remove_nodes = true;
+        while remove_nodes {
+            remove_nodes = false;
+            for i in 0..connections.len() {
+                if connections[i].len() == 2 && rev_connections[i] == connections[i] {
+                    let mut f =  connections[i].iter();
+                    let (&pre, &pre_len) = f.next().unwrap();
+                    let (&next, &next_len) =f.next().unwrap();
+                    if connections[pre].remove(&i).is_some() {
+                        connections[pre].insert(next, next_len + pre_len);
+                        assert!(rev_connections[next].remove(&i).is_some());
+                        rev_connections[next].insert(pre, next_len + pre_len);
+                        connections[i].remove(&next);
+                        rev_connections[i].remove(&pre);
+                        remove_nodes = true;
+                    }
+                    if connections[next].remove(&i).is_some() {
+                        connections[next].insert(pre, next_len + pre_len);
+                        assert!(rev_connections[pre].remove(&i).is_some());
+                        rev_connections[pre].insert(next, next_len + pre_len);
+                        connections[i].remove(&pre);
+                        rev_connections[i].remove(&next);
+                        remove_nodes =true
+                    }
+                }
+            }
+        }
+
+        let mut graph_connections = HashMap::new();
+        for i in 0..connections.len() {
+            if !connections[i].is_empty() {
+                graph_connections.insert(i, connections[i].clone());
+            }
+        }
+
+        Graph {
+            connections: graph_connections,
+            start_id,
+            end_id,
+        }
+    }
+}
+
+
+// None when the slopes keep every hike from the end
+fn find_longest_path(graph: &Graph) -> Option<usize> {
+    let mut path_ids = HashSet::new();
+    path_ids.insert(graph.start_id);
+    dfs(graph, &graph.start_id, &mut path_ids)
+}
+
+fn dfs(graph: &Graph, id: &usize, path_ids: &mut HashSet<usize>) -> Option<usize> {
+    if *id == graph.end_id {
+        return Some(0);
+    }
+    if !graph.connections.contains_key(id) {
+        return None;
+    }
+
+    let mut max_distance = None;
+
+    for (next_id, &next_steps) in graph.connections[id].iter() {
+        if path_ids.contains(next_id) {
+            continue;
+        }
+        path_ids.insert(*next_id);
+        if let Some(distance) = dfs(graph, next_id, path_ids) {
+            if max_distance.is_none() || max_distance.unwrap() < distance + next_steps {
+                max_distance.replace(distance + next_steps);
+            }
+        }
+        path_ids.remove(next_id);
+    }
+    max_distance
+}
+
+// same search as `dfs`, but keeps the ids along the longest path
+fn longest_route(
+    graph: &Graph,
+    id: usize,
+    distance: usize,
+    route: &mut Vec<usize>,
+    best: &mut Option<(usize, Vec<usize>)>,
+) {
+    if id == graph.end_id {
+        if best.as_ref().is_none_or(|(max, _)| *max < distance) {
+            best.replace((distance, route.clone()));
+        }
+        return;
+    }
+    let Some(connections) = graph.connections.get(&id) else {
+        return;
+    };
+    for (&next_id, &next_steps) in connections.iter() {
+        if route.contains(&next_id) {
+            continue;
+        }
+        route.push(next_id);
+        longest_route(graph, next_id, distance + next_steps, route, best);
+        route.pop();
+    }
+}
+
+impl Map {
+    // the tiles of the corridor `from` -> `to` that is `len` steps long
+    fn corridor(&self, from: Pos, to: Pos, len: usize, nodes: &HashSet<Pos>) -> Option<Vec<Pos>> {
+        for first in self.grids.neighbours4(from) {
+            if self.grids[first] == Tile::Forest {
+                continue;
+            }
+            let mut tiles = vec![from, first];
+            let (mut pre, mut cur) = (from, first);
+            while cur != to && !nodes.contains(&cur) {
+                let Some(next) = self
+                    .grids
+                    .neighbours4(cur)
+                    .find(|next| *next != pre && self.grids[*next] != Tile::Forest)
+                else {
+                    break;
+                };
+                tiles.push(next);
+                (pre, cur) = (cur, next);
+            }
+            if cur == to && tiles.len() == len + 1 {
+                return Some(tiles);
+            }
+        }
+        None
+    }
+}
+
+// the longest hike of part 1 over the map, None when there is none
+pub fn render(input: &str) -> Option<Picture> {
+    let map = Map::from(input, true).ok()?;
+    let graph = map.build_graph();
+    let mut best = None;
+    longest_route(&graph, graph.start_id, 0, &mut vec![graph.start_id], &mut best);
+    let (_, route) = best?;
+
+    let positions: HashMap<usize, Pos> = map.ids.iter().map(|(&pos, &id)| (id, pos)).collect();
+    let mut nodes: HashSet<Pos> = graph.connections.keys().map(|id| positions[id]).collect();
+    nodes.insert(positions[&graph.end_id]);
+    let mut hike = vec![positions[&graph.start_id]];
+    for pair in route.windows(2) {
+        let len = graph.connections[&pair[0]][&pair[1]];
+        let tiles = map
+            .corridor(positions[&pair[0]], positions[&pair[1]], len, &nodes)
+            .expect("Failed to follow a corridor");
+        hike.extend(&tiles[1..]);
+    }
+
+    let mut picture = Picture::from_grid(&map.grids, |_, tile| match tile {
+        Tile::Forest => Rgb::GREEN,
+        Tile::Path => Rgb::DARK,
+        Tile::Slope(_) => Rgb::GREY,
+    });
+    picture.path(&hike, Rgb::RED);
+    Some(picture)
+}
+
+// the map needs a start and an end with a way between them, slopes aside.
+// part1 and part2 rely on it being checked here
+pub fn validate_map(input: &str) -> Result<(), ParseError> {
+    let grids: Grid<Tile> = Grid::parse(input)?;
+    let mut open = grids.iter().filter(|(_, tile)| **tile != Tile::Forest).map(|(pos, _)| pos);
+    let (Some(start), Some(end)) = (open.next(), open.last()) else {
+        return Err(ParseError::eof(grids.rows() + 1, "the map needs a start and an end"));
+    };
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(pos) = stack.pop() {
+        for next in grids.neighbours4(pos) {
+            if grids[next] != Tile::Forest && seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    if !seen.contains(&end) {
+        return Err(ParseError::new(
+            end.row + 1,
+            end.col + 1,
+            grids[end].to_char().to_string(),
+            "the end can't be reached from the start",
+        ));
+    }
+    Ok(())
+}
+
+// None when the slopes keep every hike from the end
+pub fn part1(input: &str) -> Option<usize> {
+    let map = Map::from(input, true).ok()?;
+    find_longest_path(&map.build_graph())
+}
+
+pub fn part2(input: &str) -> Option<usize> {
+    let map = Map::from(input, false).ok()?;
+    find_longest_path(&map.build_graph())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        validate_map(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
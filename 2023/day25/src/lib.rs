@@ -0,0 +1,153 @@
+use common::{lines, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    ids: HashMap<String, usize>, // plugin names and node id mapping
+    nodes: HashMap<usize, HashSet<String>>, // plugin names contains in that node
+    connections: HashMap<usize, HashMap<usize, usize>>, // connection between nodes
+}
+
+impl Graph {
+    pub fn from(input: &str) -> Result<Self, ParseError> {
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut nodes: HashMap<usize, HashSet<String>> = HashMap::new();
+        let mut connections: HashMap<usize, HashMap<usize, usize>> = HashMap::new();
+        for (line, text) in lines(input) {
+            if text.trim().is_empty() {
+                continue;
+            }
+            let Some((name, links)) = text.split_once(':') else {
+                return Err(ParseError::line(line, text, "expected a component like jqt: rhn xhk"));
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ParseError::line(line, text, "expected a component name"));
+            }
+            if !ids.contains_key(name) {
+                let id = ids.len();
+                ids.insert(name.to_string(), id);
+                nodes.insert(id, HashSet::from([name.to_string()]));
+                connections.insert(id, HashMap::new());
+            }
+            let id = *ids.get(name).unwrap();
+
+            for nb_name in links.split_whitespace() {
+                let nb_name = nb_name.trim();
+                if !ids.contains_key(nb_name) {
+                    let nb_id = ids.len();
+                    ids.insert(nb_name.to_string(), nb_id);
+                    nodes.insert(nb_id, HashSet::from([nb_name.to_string()]));
+                    connections.insert(nb_id, HashMap::new());
+                }
+                let nb_id = *ids.get(nb_name).unwrap();
+                connections.get_mut(&id).unwrap().insert(nb_id, 1);
+                connections.get_mut(&nb_id).unwrap().insert(id, 1);
+            }
+        }
+        Ok(Self {
+            ids,
+            nodes,
+            connections,
+        })
+    }
+}
+
+impl Graph {
+    fn merge_nodes(&mut self, node1: &usize, node2: &usize) {
+        let names = self.nodes.remove(node2).unwrap();
+        for name in names.iter() {
+            self.ids.insert(name.clone(), *node1);
+        }
+        self.nodes.get_mut(node1).unwrap().extend(names);
+        let mut node_cons = self.connections.remove(node2).unwrap();
+        node_cons.remove(node1);
+        self.connections.get_mut(node1).unwrap().remove(node2);
+        for (id, &w) in node_cons.iter() {
+            assert_eq!(
+                w,
+                self.connections.get_mut(id).unwrap().remove(node2).unwrap()
+            );
+            let new_w = self
+                .connections
+                .get_mut(node1)
+                .unwrap()
+                .remove(id)
+                .unwrap_or(0)
+                + w;
+            self.connections.get_mut(node1).unwrap().insert(*id, new_w);
+            self.connections.get_mut(id).unwrap().insert(*node1, new_w);
+        }
+    }
+}
+
+// use [stoer-wagner Algorithm](https://dl.acm.org/doi/pdf/10.1145/263867.263872)
+
+fn minimun_cut_phase(graph: &Graph, start: &usize) -> Option<(usize, usize, usize)> {
+    if graph.nodes.len() < 2 || !graph.nodes.contains_key(start) {
+        return None;
+    }
+    let mut weights: HashMap<usize, usize> = HashMap::with_capacity(graph.nodes.len());
+
+    let mut last = Some(*start);
+    let mut pre = None;
+    let mut groups = HashSet::new();
+    for _ in 1..graph.nodes.len() {
+        // nothing left to add when the graph falls apart
+        let current = last?;
+        groups.insert(current);
+        weights.remove(&current);
+        for (&nb_id, &w) in graph.connections[&current].iter() {
+            if groups.contains(&nb_id) {
+                continue;
+            }
+            let new_w = weights.remove(&nb_id).unwrap_or(0) + w;
+            weights.insert(nb_id, new_w);
+        }
+        pre.replace(current);
+        last = None;
+        let mut max_weight = 0;
+        for (&id, &w) in weights.iter() {
+            if w > max_weight {
+                max_weight = w;
+                last.replace(id);
+            }
+        }
+    }
+    let last = last?;
+    Some((weights.remove(&last)?, last, pre?))
+}
+
+// None when no 3 wires split the components in two
+pub fn calculate_part1(mut graph: Graph) -> Option<usize> {
+    let start = 0;
+    while let Some((cut_phase, last, pre)) = minimun_cut_phase(&graph, &start) {
+        if cut_phase == 3 {
+            let group1 = graph.nodes.get(&last).unwrap().len();
+            let group2 = graph.ids.len() - group1;
+            return Some(group1 * group2);
+        } else {
+            graph.merge_nodes(&pre, &last);
+        }
+    }
+    None
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Graph::from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        calculate_part1(input.clone()).into()
+    }
+
+    // the last day only has one puzzle
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
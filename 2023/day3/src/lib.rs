@@ -0,0 +1,362 @@
+use common::{error, Answer, ParseError, Solution};
+use grid::{Cell, Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Digit(u32),
+    Symbol(char),
+}
+
+impl Cell for Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '0'..='9' => c.to_digit(10).map(Self::Digit),
+            _ if c.is_ascii_graphic() => Some(Self::Symbol(c)),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Self::Empty => '.',
+            Self::Digit(d) => char::from_digit(*d, 10).unwrap_or('?'),
+            Self::Symbol(c) => *c,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency {
+    // the 8 tiles around, diagonals too, as the puzzle has it
+    Touching,
+    Orthogonal,
+}
+
+// the digits of a number sit in `row`, from column `start` up to `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u64,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Number {
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.start..self.end).map(|col| Pos::new(self.row, col))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub pos: Pos,
+    pub symbol: char,
+}
+
+// a symbol with what its adjacent numbers add and multiply up to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub symbol: Symbol,
+    pub numbers: Vec<Number>,
+    pub sum: u128,
+    // None past u128
+    pub product: Option<u128>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub grid: Grid<Tile>,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    // the index in `numbers` of the number covering each tile
+    owner: Grid<Option<usize>>,
+}
+
+impl Schematic {
+    // an error for a number that doesn't fit in a u64
+    pub fn new(grid: Grid<Tile>) -> Result<Self, ParseError> {
+        let mut numbers: Vec<Number> = Vec::new();
+        let mut symbols = Vec::new();
+        let mut owner = Grid::new(grid.rows(), grid.cols(), None);
+        for (pos, tile) in grid.iter() {
+            match tile {
+                Tile::Digit(d) => {
+                    // a digit right after the last number carries it on
+                    match numbers.last_mut() {
+                        Some(n) if n.row == pos.row && n.end == pos.col => {
+                            n.value = n
+                                .value
+                                .checked_mul(10)
+                                .and_then(|v| v.checked_add(*d as u64))
+                                .ok_or_else(|| too_large(&grid, n))?;
+                            n.end += 1;
+                        }
+                        _ => numbers.push(Number {
+                            value: *d as u64,
+                            row: pos.row,
+                            start: pos.col,
+                            end: pos.col + 1,
+                        }),
+                    }
+                    owner[pos] = Some(numbers.len() - 1);
+                }
+                Tile::Symbol(c) => symbols.push(Symbol { pos, symbol: *c }),
+                Tile::Empty => {}
+            }
+        }
+        Ok(Self {
+            grid,
+            numbers,
+            symbols,
+            owner,
+        })
+    }
+
+    fn neighbours(&self, pos: Pos, adjacency: Adjacency) -> Vec<Pos> {
+        match adjacency {
+            Adjacency::Touching => self.grid.neighbours8(pos).collect(),
+            Adjacency::Orthogonal => self.grid.neighbours4(pos).collect(),
+        }
+    }
+
+    // the numbers next to a symbol, each once, in reading order
+    pub fn numbers_around(&self, symbol: &Symbol, adjacency: Adjacency) -> Vec<Number> {
+        let mut found: Vec<usize> = self
+            .neighbours(symbol.pos, adjacency)
+            .into_iter()
+            .filter_map(|pos| self.owner[pos])
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found.into_iter().map(|idx| self.numbers[idx]).collect()
+    }
+
+    // the numbers next to any symbol `is_part` accepts, each once
+    pub fn numbers_adjacent_to(
+        &self,
+        is_part: impl Fn(char) -> bool,
+        adjacency: Adjacency,
+    ) -> Vec<Number> {
+        let mut adjacent = vec![false; self.numbers.len()];
+        for symbol in self.symbols.iter().filter(|s| is_part(s.symbol)) {
+            for pos in self.neighbours(symbol.pos, adjacency) {
+                if let Some(idx) = self.owner[pos] {
+                    adjacent[idx] = true;
+                }
+            }
+        }
+        self.numbers
+            .iter()
+            .zip(adjacent)
+            .filter_map(|(number, adjacent)| adjacent.then_some(*number))
+            .collect()
+    }
+
+    pub fn aggregates(
+        &self,
+        is_part: impl Fn(char) -> bool,
+        adjacency: Adjacency,
+    ) -> Vec<Aggregate> {
+        self.symbols
+            .iter()
+            .filter(|s| is_part(s.symbol))
+            .map(|symbol| {
+                let numbers = self.numbers_around(symbol, adjacency);
+                Aggregate {
+                    symbol: *symbol,
+                    sum: numbers.iter().map(|n| n.value as u128).sum(),
+                    product: numbers
+                        .iter()
+                        .try_fold(1u128, |product, n| product.checked_mul(n.value as u128)),
+                    numbers,
+                }
+            })
+            .collect()
+    }
+
+    // the `gear` symbols with exactly `k` numbers next to them
+    pub fn gears(&self, gear: char, k: usize, adjacency: Adjacency) -> Vec<Aggregate> {
+        self.aggregates(|c| c == gear, adjacency)
+            .into_iter()
+            .filter(|a| a.numbers.len() == k)
+            .collect()
+    }
+
+    // one line per symbol and per number with 0-based coordinates, and the
+    // coordinates of what they are next to
+    pub fn to_csv(&self, adjacency: Adjacency) -> String {
+        let coords = |positions: &mut dyn Iterator<Item = Pos>| {
+            positions
+                .map(|p| format!("{}:{}", p.row, p.col))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut csv = String::from("kind,row,col,len,value,adjacent\n");
+        let aggregates = self.aggregates(|_| true, adjacency);
+        for a in aggregates.iter() {
+            let mut around = a.numbers.iter().map(|n| Pos::new(n.row, n.start));
+            csv.push_str(&format!(
+                "symbol,{},{},1,{},{}\n",
+                a.symbol.pos.row,
+                a.symbol.pos.col,
+                csv_field(&a.symbol.symbol.to_string()),
+                coords(&mut around)
+            ));
+        }
+        for number in self.numbers.iter() {
+            let start = Pos::new(number.row, number.start);
+            let mut around = aggregates
+                .iter()
+                .filter(|a| a.numbers.iter().any(|n| Pos::new(n.row, n.start) == start))
+                .map(|a| a.symbol.pos);
+            csv.push_str(&format!(
+                "number,{},{},{},{},{}\n",
+                number.row,
+                number.start,
+                number.end - number.start,
+                number.value,
+                coords(&mut around)
+            ));
+        }
+        csv
+    }
+}
+
+// quoted as RFC 4180 asks when it holds a comma, a quote or a line break,
+// with the quotes inside doubled
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// the error for `number`, with all of its digits, the ones past where it
+// overflowed too
+fn too_large(grid: &Grid<Tile>, number: &Number) -> ParseError {
+    let digits: String = (number.start..grid.cols())
+        .map(|col| grid[Pos::new(number.row, col)])
+        .map_while(|tile| matches!(tile, Tile::Digit(_)).then(|| tile.to_char()))
+        .collect();
+    ParseError::new(
+        number.row + 1,
+        number.start + 1,
+        digits,
+        "the number does not fit in a u64",
+    )
+}
+
+pub fn read_schematic(input: &str) -> Result<Schematic, ParseError> {
+    Schematic::new(Grid::parse(input)?)
+}
+
+pub fn part1(schematic: &Schematic) -> u128 {
+    schematic
+        .numbers_adjacent_to(|_| true, Adjacency::Touching)
+        .iter()
+        .map(|n| n.value as u128)
+        .sum()
+}
+
+// None past u128
+pub fn part2(schematic: &Schematic) -> Option<u128> {
+    schematic
+        .gears('*', 2, Adjacency::Touching)
+        .iter()
+        .try_fold(0u128, |total, gear| total.checked_add(gear.product?))
+}
+
+// past what an answer holds is an error, not a wrong number
+fn to_answer(value: Option<u128>, part: u32) -> Answer {
+    match value.and_then(|v| i128::try_from(v).ok()) {
+        Some(v) => v.into(),
+        None => {
+            error!("Part {} overflowed", part);
+            Answer::Unsolved
+        }
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_schematic(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        to_answer(Some(part1(input)), 1)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        to_answer(part2(input), 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2, read_schematic, Adjacency};
+
+    const EXAMPLE: &str = include_str!("../example_input");
+
+    #[test]
+    fn test_example() {
+        let schematic = read_schematic(EXAMPLE).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(part1(&schematic), 4361);
+        assert_eq!(part2(&schematic), Some(467835));
+
+        let orthogonal = schematic.numbers_adjacent_to(|_| true, Adjacency::Orthogonal);
+        let values: Vec<u64> = orthogonal.iter().map(|n| n.value).collect();
+        assert_eq!(values, vec![35, 633, 617, 664, 598]);
+        let gears = schematic.gears('*', 1, Adjacency::Touching);
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].sum, 617);
+    }
+
+    #[test]
+    fn test_edges() {
+        // a gear in the first column, and numbers running into the edge
+        let schematic = read_schematic("*12\n3..\n..4\n").unwrap();
+        assert_eq!(part2(&schematic), Some(36));
+        assert_eq!(part1(&schematic), 15);
+        assert_eq!(
+            schematic.to_csv(Adjacency::Touching),
+            "kind,row,col,len,value,adjacent\n\
+             symbol,0,0,1,*,0:1 1:0\n\
+             number,0,1,2,12,0:0\n\
+             number,1,0,1,3,0:0\n\
+             number,2,2,1,4,\n"
+        );
+        // symbols that mean something in CSV are quoted
+        let schematic = read_schematic("1,.\n..\"\n.2.\n").unwrap();
+        assert_eq!(
+            schematic.to_csv(Adjacency::Touching),
+            "kind,row,col,len,value,adjacent\n\
+             symbol,0,1,1,\",\",0:0\n\
+             symbol,1,2,1,\"\"\"\",2:1\n\
+             number,0,0,1,1,0:1\n\
+             number,2,1,1,2,1:2\n"
+        );
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        // past a u32, and then past a u64
+        let schematic = read_schematic("12345678901*\n").unwrap();
+        assert_eq!(part1(&schematic), 12345678901);
+        let schematic = read_schematic("9999999999*\n#9999999999\n").unwrap();
+        assert_eq!(part2(&schematic), Some(99999999980000000001));
+        let e = read_schematic("...........................\n*.123456789012345678901234.\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.text.as_str()),
+            (2, 3, "123456789012345678901234")
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use common::{error, lines, parse_number, Answer, ParseError, Solution};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    // from the "Card N:" prefix
+    pub id: u64,
+    pub winning: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    // "Card N: <numbers> | <numbers>"
+    pub fn parse(line: usize, text: &str) -> Result<Self, ParseError> {
+        let (card, numbers) = text
+            .split_once(':')
+            .ok_or_else(|| ParseError::line(line, text, "expected \"Card N:\""))?;
+        let id = card
+            .strip_prefix("Card")
+            .ok_or_else(|| ParseError::at(line, text, card, "expected \"Card N:\""))?;
+        let id = parse_number(line, text, id)?;
+        let (winning, numbers) = numbers
+            .split_once('|')
+            .ok_or_else(|| ParseError::at(line, text, numbers, "expected '|' between the numbers"))?;
+        let parse_all = |numbers: &str| {
+            numbers
+                .split_whitespace()
+                .map(|num| parse_number(line, text, num))
+                .collect::<Result<Vec<u32>, _>>()
+        };
+        let mut winning = parse_all(winning)?;
+        winning.sort_unstable();
+        Ok(Self {
+            id,
+            winning,
+            numbers: parse_all(numbers)?,
+        })
+    }
+
+    pub fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|num| self.winning.binary_search(num).is_ok())
+            .count()
+    }
+
+    // 1 for the first match, doubled for each one after, None past u128
+    pub fn points(&self) -> Option<u128> {
+        match self.matches() {
+            0 => Some(0),
+            n => 1u128.checked_shl(u32::try_from(n - 1).ok()?),
+        }
+    }
+}
+
+pub fn read_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    let mut cards: Vec<Card> = Vec::new();
+    let mut seen = HashMap::new();
+    for (line, text) in lines(input).filter(|(_, text)| !text.trim().is_empty()) {
+        let card = Card::parse(line, text)?;
+        if let Some(first) = seen.insert(card.id, line) {
+            return Err(ParseError::line(
+                line,
+                text,
+                format!("card {} is already on line {}", card.id, first),
+            ));
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+// where the copies of one card came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub id: u64,
+    // the original included
+    pub copies: u128,
+    // the earlier cards that won copies of this one, and how many
+    pub from: Vec<(u64, u128)>,
+}
+
+// the cards after all the copies are won, by card number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cascade {
+    pub cards: Vec<Provenance>,
+}
+
+impl Cascade {
+    // card N wins copies of the cards numbered N + 1 and on, one per match.
+    // None when the copies don't fit in a u128
+    pub fn new(cards: &[Card]) -> Option<Self> {
+        let mut order: Vec<&Card> = cards.iter().collect();
+        order.sort_unstable_by_key(|card| card.id);
+        let index: HashMap<u64, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| (card.id, idx))
+            .collect();
+        let mut provenance: Vec<Provenance> = order
+            .iter()
+            .map(|card| Provenance {
+                id: card.id,
+                copies: 1,
+                from: Vec::new(),
+            })
+            .collect();
+        for (idx, card) in order.iter().enumerate() {
+            let copies = provenance[idx].copies;
+            for won in 1..=card.matches() as u64 {
+                let Some(&target) = card.id.checked_add(won).and_then(|id| index.get(&id)) else {
+                    continue;
+                };
+                let target = &mut provenance[target];
+                target.copies = target.copies.checked_add(copies)?;
+                target.from.push((card.id, copies));
+            }
+        }
+        Some(Self { cards: provenance })
+    }
+
+    pub fn total(&self) -> Option<u128> {
+        self.cards
+            .iter()
+            .try_fold(0u128, |total, card| total.checked_add(card.copies))
+    }
+
+    // how many cards ended up with each number of copies
+    pub fn histogram(&self) -> BTreeMap<u128, usize> {
+        let mut histogram = BTreeMap::new();
+        for card in self.cards.iter() {
+            *histogram.entry(card.copies).or_default() += 1;
+        }
+        histogram
+    }
+}
+
+// part 2 a card at a time, the cards have to come in order of their numbers.
+// Only the copies won of cards still to come are kept
+#[derive(Debug)]
+pub struct Part2 {
+    // None once the copies don't fit in a u128
+    total: Option<u128>,
+    last: Option<u64>,
+    pending: BTreeMap<u64, u128>,
+}
+
+impl Default for Part2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Part2 {
+    pub fn new() -> Self {
+        Self {
+            total: Some(0),
+            last: None,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    // Err with the number of the card before, when `card` doesn't come after it
+    pub fn feed_card(&mut self, card: &Card) -> Result<(), u64> {
+        if let Some(last) = self.last.filter(|last| *last >= card.id) {
+            return Err(last);
+        }
+        self.last = Some(card.id);
+        if self.total.is_some() {
+            self.total = self.add(card);
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, card: &Card) -> Option<u128> {
+        // copies won of cards missing from the deck go nowhere
+        self.pending = self.pending.split_off(&card.id);
+        let copies = self.pending.remove(&card.id).unwrap_or(0).checked_add(1)?;
+        for won in 1..=card.matches() as u64 {
+            let Some(id) = card.id.checked_add(won) else {
+                break;
+            };
+            let pending = self.pending.entry(id).or_default();
+            *pending = pending.checked_add(copies)?;
+        }
+        self.total?.checked_add(copies)
+    }
+
+    pub fn end(self) -> Option<u128> {
+        self.total
+    }
+}
+
+pub fn part1(cards: &[Card]) -> Option<u128> {
+    cards
+        .iter()
+        .try_fold(0u128, |total, card| total.checked_add(card.points()?))
+}
+
+pub fn part2(cards: &[Card]) -> Option<u128> {
+    Cascade::new(cards)?.total()
+}
+
+// past what an answer holds is an error, not a wrong number
+fn to_answer(value: Option<u128>, part: u32) -> Answer {
+    match value.and_then(|v| i128::try_from(v).ok()) {
+        Some(v) => v.into(),
+        None => {
+            error!("Part {} overflowed", part);
+            Answer::Unsolved
+        }
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_cards(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        to_answer(part1(input), 1)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        to_answer(part2(input), 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2, read_cards, Card, Cascade, Part2};
+
+    // the cards fed one at a time, as the binary does
+    fn streamed(cards: &[Card]) -> Option<u128> {
+        let mut part2 = Part2::new();
+        for card in cards {
+            part2.feed_card(card).unwrap();
+        }
+        part2.end()
+    }
+
+    const EXAMPLE: &str = include_str!("../example_input");
+
+    #[test]
+    fn test_example() {
+        let cards = read_cards(EXAMPLE).unwrap();
+        assert_eq!(part1(&cards), Some(13));
+        assert_eq!(part2(&cards), Some(30));
+        assert_eq!(streamed(&cards), Some(30));
+        // card 4 is missing, the copies card 3 wins of it go nowhere
+        let gap = [cards[0].clone(), cards[1].clone(), cards[2].clone(), cards[4].clone()];
+        assert_eq!(streamed(&gap), part2(&gap));
+        let mut part2 = Part2::new();
+        assert_eq!(part2.feed_card(&cards[1]), Ok(()));
+        assert_eq!(part2.feed_card(&cards[0]), Err(2));
+
+        let cascade = Cascade::new(&cards).unwrap();
+        let copies: Vec<u128> = cascade.cards.iter().map(|c| c.copies).collect();
+        assert_eq!(copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.cards[3].from, vec![(1, 1), (2, 2), (3, 4)]);
+        assert_eq!(
+            cascade.histogram().into_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (4, 1), (8, 1), (14, 1)]
+        );
+    }
+
+    #[test]
+    fn test_large_decks() {
+        // every card matches all 100 numbers, and the numbering starts at 10
+        let numbers: Vec<String> = (1..=100).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let deck: String = (0..130)
+            .map(|n| format!("Card {}: {} | {}\n", 10 + n, numbers, numbers))
+            .collect();
+        let cards = read_cards(&deck).unwrap();
+        assert_eq!(cards[0].id, 10);
+        assert_eq!(cards[0].points(), Some(1 << 99));
+        assert_eq!(part1(&cards[..2]), Some(1 << 100));
+        // the copies double card after card, until a u128 can't hold them
+        let cascade = Cascade::new(&cards[..100]).unwrap();
+        assert_eq!(cascade.cards[99].copies, 1 << 99);
+        assert_eq!(part2(&cards[..100]), Some((1 << 100) - 1));
+        assert_eq!(part2(&cards), None);
+        assert_eq!(streamed(&cards[..100]), Some((1 << 100) - 1));
+        assert_eq!(streamed(&cards), None);
+
+        let card = Card::parse(1, "Card 3: 1 | 1").unwrap();
+        assert_eq!((card.id, card.points()), (3, Some(1)));
+        assert!(read_cards("Card 1: 1 | 2\nCard 1: 3 | 4\n").is_err());
+    }
+}
@@ -0,0 +1,895 @@
+use common::{debug, parse_number, Answer, ParseError, Solution};
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt,
+    io::BufRead,
+};
+
+#[cfg(test)]
+mod oracle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapElement {
+    pub dst: u64,
+    pub src: u64,
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GardenMapper {
+    mappings: Vec<MapElement>,
+}
+
+// maps applied one after another, folded into a single map
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GardenChain {
+    stages: Vec<GardenMapper>,
+    composed: GardenMapper,
+}
+
+impl GardenChain {
+    pub fn new(stages: Vec<GardenMapper>) -> Self {
+        let composed = stages
+            .iter()
+            .fold(GardenMapper::default(), |composed, stage| composed.compose(stage));
+        Self { stages, composed }
+    }
+
+    pub fn stages(&self) -> &[GardenMapper] {
+        &self.stages
+    }
+
+    pub fn composed(&self) -> &GardenMapper {
+        &self.composed
+    }
+
+    pub fn map_source(&self, src: u64) -> u64 {
+        self.composed.map_source(src)
+    }
+
+    pub fn map_ranges(&self, ranges: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        self.composed.map_ranges(ranges)
+    }
+
+    pub fn preimage_ranges(&self, range: (u64, u64)) -> Vec<(u64, u64)> {
+        self.composed.preimage_ranges(range)
+    }
+}
+
+// one "<from>-to-<to> map:" block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub from: String,
+    pub to: String,
+    // where the header is, for errors
+    pub line: usize,
+    pub mapper: GardenMapper,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineError {
+    // `from` only leads to the `reached` categories
+    MissingLink {
+        from: String,
+        to: String,
+        reached: Vec<String>,
+    },
+    // the categories in the order the maps go round
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLink { from, to, reached } if reached.is_empty() => {
+                write!(f, "no map leads from {} to {}, nothing maps {}", from, to, from)
+            }
+            Self::MissingLink { from, to, reached } => write!(
+                f,
+                "no map leads from {} to {}, {} only reaches {}",
+                from,
+                to,
+                from,
+                reached.join(", ")
+            ),
+            Self::Cycle(categories) => write!(f, "the maps go round in a cycle {}", categories.join(" -> ")),
+        }
+    }
+}
+
+impl Error for PipelineError {}
+
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    seeds_ranges: Vec<(u64, u64)>,
+    maps: Vec<CategoryMap>,
+    // the maps from the seeds to the category asked for
+    chain: GardenChain,
+}
+
+impl Almanac {
+    // the seeds to their locations
+    pub fn read<R: BufRead>(reader: &mut R) -> Result<Self, ParseError> {
+        Self::read_between(reader, "seed", "location", OverlapPolicy::default())
+    }
+
+    // the maps may come in any order, the path from `from` to `to` is found
+    // through their categories
+    pub fn read_between<R: BufRead>(
+        reader: &mut R,
+        from: &str,
+        to: &str,
+        policy: OverlapPolicy,
+    ) -> Result<Self, ParseError> {
+        let mut reader = LineReader { reader, line: 0 };
+        let (seeds, seeds_ranges) = read_seeds(&mut reader)?;
+        let mut maps: Vec<CategoryMap> = Vec::new();
+        while let Some(map) = read_map(&mut reader, policy)? {
+            if let Some(other) = maps.iter().find(|m| m.from == map.from && m.to == map.to) {
+                return Err(ParseError::line(
+                    map.line,
+                    format!("{}-to-{} map:", map.from, map.to),
+                    format!("the map is already on line {}", other.line),
+                ));
+            }
+            maps.push(map);
+        }
+        let mut almanac = Self {
+            seeds,
+            seeds_ranges,
+            maps,
+            chain: GardenChain::default(),
+        };
+        if let Some(cycle) = almanac.find_cycle() {
+            // the header of the map that closes the cycle
+            let last = almanac
+                .maps
+                .iter()
+                .filter(|m| cycle.windows(2).any(|w| m.from == w[0] && m.to == w[1]))
+                .max_by_key(|m| m.line)
+                .expect("A cycle is made of maps");
+            return Err(ParseError::line(
+                last.line,
+                format!("{}-to-{} map:", last.from, last.to),
+                PipelineError::Cycle(cycle).to_string(),
+            ));
+        }
+        let chain = almanac
+            .chain_between(from, to)
+            .map_err(|e| almanac.pipeline_error(from, e, reader.line))?;
+        almanac.chain = chain;
+        Ok(almanac)
+    }
+
+    // a missing link is put on the last map into a category no map leaves,
+    // at the end of the input when no map leads anywhere
+    fn pipeline_error(&self, from: &str, error: PipelineError, last_line: usize) -> ParseError {
+        let dead_end = match &error {
+            PipelineError::MissingLink { reached, .. } => self
+                .maps
+                .iter()
+                .filter(|m| m.from == from || reached.contains(&m.from))
+                .filter(|m| !self.maps.iter().any(|next| next.from == m.to))
+                .max_by_key(|m| m.line),
+            PipelineError::Cycle(_) => None,
+        };
+        match dead_end {
+            Some(map) => ParseError::line(map.line, format!("{}-to-{} map:", map.from, map.to), error.to_string()),
+            None => ParseError::eof(last_line, error.to_string()),
+        }
+    }
+
+    pub fn maps(&self) -> &[CategoryMap] {
+        &self.maps
+    }
+
+    // every category, in the order they first show up
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = Vec::new();
+        for map in self.maps.iter() {
+            for category in [map.from.as_str(), map.to.as_str()] {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+        categories
+    }
+
+    // the categories of the first cycle found, back to where it started
+    fn find_cycle(&self) -> Option<Vec<String>> {
+        // 1 while on the current path, 2 once everything after it is done
+        fn visit<'a>(
+            almanac: &'a Almanac,
+            category: &'a str,
+            state: &mut HashMap<&'a str, u8>,
+            path: &mut Vec<&'a str>,
+        ) -> Option<Vec<String>> {
+            match state.get(category) {
+                Some(1) => {
+                    let start = path.iter().position(|c| *c == category).unwrap_or(0);
+                    let mut cycle: Vec<String> = path[start..].iter().map(|c| c.to_string()).collect();
+                    cycle.push(category.to_string());
+                    return Some(cycle);
+                }
+                Some(_) => return None,
+                None => {}
+            }
+            state.insert(category, 1);
+            path.push(category);
+            for map in almanac.maps.iter().filter(|m| m.from == category) {
+                if let Some(cycle) = visit(almanac, &map.to, state, path) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            state.insert(category, 2);
+            None
+        }
+
+        let mut state = HashMap::new();
+        self.categories()
+            .into_iter()
+            .find_map(|category| visit(self, category, &mut state, &mut Vec::new()))
+    }
+
+    // the fewest maps that take `from` to `to`
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap>, PipelineError> {
+        let mut came_by: HashMap<&str, Option<&CategoryMap>> = HashMap::from([(from, None)]);
+        let mut bfs = VecDeque::from([from]);
+        while let Some(category) = bfs.pop_front() {
+            if category == to {
+                let mut path = Vec::new();
+                let mut at = to;
+                while let Some(Some(map)) = came_by.get(at) {
+                    path.push(*map);
+                    at = &map.from;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            for map in self.maps.iter().filter(|m| m.from == category) {
+                if !came_by.contains_key(map.to.as_str()) {
+                    came_by.insert(&map.to, Some(map));
+                    bfs.push_back(&map.to);
+                }
+            }
+        }
+        let mut reached: Vec<String> = came_by
+            .keys()
+            .filter(|c| **c != from)
+            .map(|c| c.to_string())
+            .collect();
+        reached.sort();
+        Err(PipelineError::MissingLink {
+            from: from.to_string(),
+            to: to.to_string(),
+            reached,
+        })
+    }
+
+    pub fn chain_between(&self, from: &str, to: &str) -> Result<GardenChain, PipelineError> {
+        let path = self.path(from, to)?;
+        debug!(
+            "{} via {}",
+            from,
+            path.iter().map(|m| m.to.as_str()).collect::<Vec<_>>().join(", ")
+        );
+        Ok(GardenChain::new(path.into_iter().map(|m| m.mapper.clone()).collect()))
+    }
+
+    // the seeds to the category asked for in one step
+    pub fn chain(&self) -> &GardenChain {
+        &self.chain
+    }
+
+    pub fn part1(&self) -> u64 {
+        self.seeds
+            .iter()
+            .map(|e| self.chain.map_source(*e))
+            .fold(u64::MAX, |cur, e| if e < cur { e } else { cur })
+    }
+
+    pub fn part2(&self) -> u64 {
+        let locations_ranges = self.chain.map_ranges(&self.seeds_ranges);
+        locations_ranges.iter().fold(u64::MAX, |cur, e| if e.0 < cur { e.0 } else { cur })
+    }
+}
+
+// what to do with map lines whose ranges overlap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    // the sources go to the line that comes first
+    FirstWins,
+    LastWins,
+    // overlapping sources or destinations are an error
+    #[default]
+    Error,
+}
+
+impl OverlapPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first" => Some(Self::FirstWins),
+            "last" => Some(Self::LastWins),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+// one line of a map, with its text for the errors. Only built through `new`,
+// so both of its ranges end by u64::MAX and the sums below can't overflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    line: usize,
+    text: String,
+    element: MapElement,
+}
+
+impl Rule {
+    pub fn new(line: usize, text: &str, element: MapElement) -> Result<Self, ParseError> {
+        if element.src.checked_add(element.length).is_none() {
+            return Err(ParseError::line(line, text, "the sources run past u64::MAX"));
+        }
+        if element.dst.checked_add(element.length).is_none() {
+            return Err(ParseError::line(line, text, "the destinations run past u64::MAX"));
+        }
+        Ok(Self {
+            line,
+            text: text.to_string(),
+            element,
+        })
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn element(&self) -> MapElement {
+        self.element
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Source,
+    Destination,
+}
+
+// two lines that claim the same numbers, `second` comes later in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    pub side: Side,
+    pub first: Rule,
+    pub second: Rule,
+    // the numbers both claim, as start and end
+    pub range: (u64, u64),
+}
+
+impl Overlap {
+    fn find(rules: &[&Rule], side: Side) -> Option<Self> {
+        let bounds = |r: &Rule| match side {
+            Side::Source => (r.element.src, r.element.src + r.element.length),
+            Side::Destination => (r.element.dst, r.element.dst + r.element.length),
+        };
+        let mut sorted = rules.to_vec();
+        sorted.sort_by_key(|r| bounds(r));
+        // the rule reaching furthest so far is the one the next may run into
+        let mut furthest: Option<&Rule> = None;
+        for rule in sorted {
+            let (start, end) = bounds(rule);
+            if let Some(other) = furthest {
+                let other_end = bounds(other).1;
+                if start < other_end {
+                    let (first, second) = if other.line < rule.line {
+                        (other.clone(), rule.clone())
+                    } else {
+                        (rule.clone(), other.clone())
+                    };
+                    return Some(Self {
+                        side,
+                        first,
+                        second,
+                        range: (start, end.min(other_end)),
+                    });
+                }
+            }
+            if furthest.is_none_or(|other| bounds(other).1 < end) {
+                furthest = Some(rule);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = match self.side {
+            Side::Source => "sources",
+            Side::Destination => "destinations",
+        };
+        write!(
+            f,
+            "lines {} and {} both map {} {}..{}",
+            self.first.line, self.second.line, side, self.range.0, self.range.1
+        )
+    }
+}
+
+impl Error for Overlap {}
+
+// the rules in order of priority, each keeps the sources no rule before it
+// took. Destinations reached twice are fine for mapping forward, `invert`
+// refuses them
+fn claim<'a>(rules: impl Iterator<Item = &'a Rule>) -> Vec<MapElement> {
+    let mut claimed: Vec<(u64, u64)> = Vec::new();
+    let mut elements = Vec::new();
+    for rule in rules {
+        let m = rule.element;
+        let mut pieces = vec![(m.src, m.src + m.length)];
+        for &(start, end) in claimed.iter() {
+            pieces = pieces
+                .into_iter()
+                .flat_map(|(s, e)| [(s, e.min(start)), (s.max(end), e)])
+                .filter(|(s, e)| s < e)
+                .collect();
+        }
+        for (start, end) in pieces {
+            elements.push(MapElement {
+                dst: m.dst + (start - m.src),
+                src: start,
+                length: end - start,
+            });
+            claimed.push((start, end));
+        }
+    }
+    elements
+}
+
+impl GardenMapper {
+    // the rules settle their overlaps as `policy` says first
+    pub fn build(rules: &[Rule], policy: OverlapPolicy) -> Result<Self, Box<Overlap>> {
+        let rules: Vec<&Rule> = rules.iter().filter(|r| r.element.length > 0).collect();
+        let input = match policy {
+            OverlapPolicy::Error => {
+                if let Some(overlap) = Overlap::find(&rules, Side::Source)
+                    .or_else(|| Overlap::find(&rules, Side::Destination))
+                {
+                    return Err(Box::new(overlap));
+                }
+                rules.iter().map(|r| r.element).collect()
+            }
+            OverlapPolicy::FirstWins => claim(rules.iter().copied()),
+            OverlapPolicy::LastWins => claim(rules.iter().rev().copied()),
+        };
+        Ok(Self::fill_gaps(input))
+    }
+
+    fn fill_gaps(mut input: Vec<MapElement>) -> Self {
+        // sources no line maps keep their number, as the puzzle says, so the
+        // gaps map to themselves rather than to the destination gaps. Filling
+        // them keeps the mappings contiguous from 0 for map_ranges
+        input.sort_by_key(|k| k.src);
+        let mut mappings = Vec::<MapElement>::with_capacity(input.len() * 2);
+        let mut gap_start = 0;
+        for element in input {
+            if element.src > gap_start {
+                mappings.push(MapElement {
+                    dst: gap_start,
+                    src: gap_start,
+                    length: element.src - gap_start,
+                });
+            }
+            gap_start = element.src + element.length;
+            mappings.push(element);
+        }
+        Self { mappings }
+    }
+
+    pub fn mappings(&self) -> &[MapElement] {
+        &self.mappings
+    }
+
+    // the mappings cover 0 up to here, everything after maps to itself
+    pub fn end(&self) -> u64 {
+        self.mappings.last().map_or(0, |m| m.src + m.length)
+    }
+
+    // `self` and then `next`, as one map. Past both ends everything maps to
+    // itself, so only the sources up to the larger end need a look
+    pub fn compose(&self, next: &GardenMapper) -> GardenMapper {
+        let end = self.end().max(next.end());
+        let mut stages = self.mappings.clone();
+        if self.end() < end {
+            stages.push(MapElement {
+                dst: self.end(),
+                src: self.end(),
+                length: end - self.end(),
+            });
+        }
+        let mut mappings: Vec<MapElement> = Vec::with_capacity(stages.len() + next.mappings.len());
+        for stage in stages.into_iter().filter(|s| s.length > 0) {
+            // the pieces come back in the order of the sources they split
+            let mut src = stage.src;
+            for (dst, length) in next.map_ranges(&[(stage.dst, stage.length)]) {
+                match mappings.last_mut() {
+                    Some(last) if last.src + last.length == src && last.dst + last.length == dst => {
+                        last.length += length;
+                    }
+                    _ => mappings.push(MapElement { dst, src, length }),
+                }
+                src += length;
+            }
+        }
+        // a tail that maps to itself is what happens past the end anyway
+        while mappings.last().is_some_and(|m| m.src == m.dst) {
+            mappings.pop();
+        }
+        Self { mappings }
+    }
+
+    // the mappings are sorted and contiguous, so a binary search finds the
+    // one holding `src`
+    pub fn map_source(&self, src: u64) -> u64 {
+        let idx = self.mappings.partition_point(|m| m.src + m.length <= src);
+        match self.mappings.get(idx) {
+            Some(r) if r.src <= src => r.dst + (src - r.src),
+            _ => src,
+        }
+    }
+
+    // the map back from destinations to sources. Only when the destinations
+    // cover 0 up to the end once each, as they do when the map shuffles
+    // whole ranges around
+    pub fn invert(&self) -> Option<GardenMapper> {
+        let mut mappings: Vec<MapElement> = self
+            .mappings
+            .iter()
+            .filter(|m| m.length > 0)
+            .map(|m| MapElement {
+                dst: m.src,
+                src: m.dst,
+                length: m.length,
+            })
+            .collect();
+        mappings.sort_by_key(|m| m.src);
+        let mut end = 0;
+        for m in mappings.iter() {
+            if m.src != end {
+                return None;
+            }
+            end += m.length;
+        }
+        (end == self.end()).then_some(Self { mappings })
+    }
+
+    // the sources that land in `range`, as sorted (start, length) ranges
+    pub fn preimage_ranges(&self, (start, len): (u64, u64)) -> Vec<(u64, u64)> {
+        let end = start.saturating_add(len);
+        let mut ranges: Vec<(u64, u64)> = self
+            .mappings
+            .iter()
+            .filter_map(|m| {
+                let from = m.dst.max(start);
+                let to = (m.dst + m.length).min(end);
+                (from < to).then(|| (m.src + (from - m.dst), to - from))
+            })
+            .collect();
+        // past the end the sources are their own destinations
+        let from = self.end().max(start);
+        if from < end {
+            ranges.push((from, end - from));
+        }
+        ranges.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (start, len) in ranges {
+            match merged.last_mut() {
+                Some(last) if last.0 + last.1 >= start => {
+                    last.1 = last.1.max(start + len - last.0);
+                }
+                _ => merged.push((start, len)),
+            }
+        }
+        merged
+    }
+
+    // the ranges may overlap, each one is split on its own
+    pub fn map_ranges(&self, ranges: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        let mut dst_ranges = Vec::new();
+        for &(mut src_start, src_len) in ranges {
+            // a range past u64::MAX stops there, as in preimage_ranges
+            let mut src_len = src_len.min(u64::MAX - src_start);
+            // the mappings are contiguous from 0, find the one holding the start
+            let mut idx = self
+                .mappings
+                .partition_point(|m| m.src + m.length <= src_start);
+            while src_len > 0 && idx < self.mappings.len() {
+                let mapping = &self.mappings[idx];
+                let len = u64::min(mapping.src + mapping.length, src_start + src_len) - src_start;
+                dst_ranges.push((src_start - mapping.src + mapping.dst, len));
+                src_start += len;
+                src_len -= len;
+                idx += 1;
+            }
+            if src_len > 0 {
+                // out of definition
+                dst_ranges.push((src_start, src_len));
+            }
+        }
+        dst_ranges
+    }
+}
+
+// counts the lines read so far, for error positions
+struct LineReader<'a, R> {
+    reader: &'a mut R,
+    line: usize,
+}
+
+impl<R: BufRead> LineReader<'_, R> {
+    // None at the end of the input
+    fn next_line(&mut self) -> Result<Option<String>, ParseError> {
+        let mut line = String::new();
+        let size = self
+            .reader
+            .read_line(&mut line)
+            .map_err(|e| ParseError::eof(self.line + 1, format!("failed to read: {}", e)))?;
+        if size == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        Ok(Some(line))
+    }
+
+    // skip blank lines, None at the end of the input
+    fn next_text_or_end(&mut self) -> Result<Option<String>, ParseError> {
+        while let Some(line) = self.next_line()? {
+            if !line.trim().is_empty() {
+                return Ok(Some(line.trim().to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    // the same, the input ending is an error
+    fn next_text(&mut self, expected: &str) -> Result<String, ParseError> {
+        self.next_text_or_end()?
+            .ok_or_else(|| ParseError::eof(self.line, format!("expected {}", expected)))
+    }
+}
+
+fn get_numbers(line: usize, line_text: &str, text: &str) -> Result<Vec<u64>, ParseError> {
+    text.split_whitespace()
+        .map(|e| parse_number(line, line_text, e))
+        .collect()
+}
+
+// the seeds, and the same numbers read as (start, length) pairs
+type Seeds = (Vec<u64>, Vec<(u64, u64)>);
+
+fn read_seeds<R: BufRead>(reader: &mut LineReader<R>) -> Result<Seeds, ParseError> {
+    let prefix = "seeds:";
+    let line = reader.next_text("the seeds line")?;
+    let Some(text) = line.strip_prefix(prefix) else {
+        return Err(ParseError::line(reader.line, line.as_str(), "expected \"seeds:\""));
+    };
+    let seeds = get_numbers(reader.line, &line, text)?;
+    if !seeds.len().is_multiple_of(2) {
+        return Err(ParseError::line(reader.line, line.as_str(), "expected pairs of seed start and length"));
+    }
+    let mut seeds_by_range = Vec::<(u64, u64)>::with_capacity(seeds.len()/2);
+    for idx in 0..seeds.len()/2 {
+        let s_start = seeds[idx*2];
+        let s_len = seeds[idx*2+1];
+        if s_start.checked_add(s_len).is_none() {
+            return Err(ParseError::line(reader.line, line.as_str(), "the seed ranges run past u64::MAX"));
+        }
+        seeds_by_range.push((s_start, s_len));
+    }
+    Ok((seeds, seeds_by_range))
+}
+
+// None once the maps run out
+fn read_map<R: BufRead>(
+    reader: &mut LineReader<R>,
+    policy: OverlapPolicy,
+) -> Result<Option<CategoryMap>, ParseError> {
+    let mut map_inputs = Vec::new();
+    let Some(text) = reader.next_text_or_end()? else {
+        return Ok(None);
+    };
+    let line = reader.line;
+    let Some((from, to)) = text
+        .strip_suffix(" map:")
+        .and_then(|header| header.split_once("-to-"))
+        .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+    else {
+        return Err(ParseError::line(line, text.as_str(), "expected \"<from>-to-<to> map:\""));
+    };
+    debug!("Read map '{}-to-{}'", from, to);
+
+    while let Some(line) = reader.next_line()? {
+        let text = line.trim();
+        if text.is_empty() {
+            break;
+        }
+        let numbers = get_numbers(reader.line, text, text)?;
+        if numbers.len() != 3 {
+            return Err(ParseError::line(reader.line, text, "expected 3 numbers"));
+        }
+        let element = MapElement {
+            dst: numbers[0],
+            src: numbers[1],
+            length: numbers[2],
+        };
+        map_inputs.push(Rule::new(reader.line, text, element)?);
+    }
+    Ok(Some(CategoryMap {
+        from: from.to_string(),
+        to: to.to_string(),
+        line,
+        mapper: GardenMapper::build(&map_inputs, policy)
+            .map_err(|e| ParseError::line(e.second.line, e.second.text.as_str(), e.to_string()))?,
+    }))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Almanac::read(&mut input.as_bytes())
+    }
+
+    fn read<R: BufRead>(mut reader: R) -> Result<Self::Input, ParseError> {
+        Almanac::read(&mut reader)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Almanac, OverlapPolicy, PipelineError};
+
+    const EXAMPLE: &str = include_str!("../example_input");
+
+    fn read(text: &str, from: &str, to: &str) -> Result<Almanac, String> {
+        Almanac::read_between(&mut text.as_bytes(), from, to, OverlapPolicy::default()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let almanac = read(EXAMPLE, "seed", "location").unwrap();
+        assert_eq!((almanac.part1(), almanac.part2()), (35, 46));
+        assert_eq!(almanac.categories().len(), 8);
+        let path: Vec<&str> = almanac.path("soil", "humidity").unwrap().iter().map(|m| m.to.as_str()).collect();
+        assert_eq!(path, vec!["fertilizer", "water", "light", "temperature", "humidity"]);
+        // seed 79 is soil 81, fertilizer 81, water 81 and light 74
+        let almanac = read(EXAMPLE, "seed", "light").unwrap();
+        assert_eq!(almanac.chain().map_source(79), 74);
+        assert_eq!(
+            almanac.path("humidity", "soil"),
+            Err(PipelineError::MissingLink {
+                from: "humidity".to_string(),
+                to: "soil".to_string(),
+                reached: vec!["location".to_string()],
+            })
+        );
+        assert!(read(EXAMPLE, "seed", "color").unwrap_err().contains("seed only reaches fertilizer"));
+
+        // the blocks in reverse give the same answers
+        let mut blocks: Vec<&str> = EXAMPLE.trim_end().split("\n\n").collect();
+        blocks[1..].reverse();
+        let almanac = read(&blocks.join("\n\n"), "seed", "location").unwrap();
+        assert_eq!((almanac.part1(), almanac.part2()), (35, 46));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let almanac = read(EXAMPLE, "seed", "location").unwrap();
+        let soil = &almanac.maps()[0].mapper;
+        let inverse = soil.invert().unwrap();
+        assert_eq!(inverse.map_source(81), 79);
+        assert_eq!(inverse.map_source(50), 98);
+        assert_eq!(inverse.invert().as_ref(), Some(soil));
+        // seed 82 is the only one of the part 2 seeds that gets to 46
+        let seeds = almanac.chain().preimage_ranges((46, 1));
+        assert!(seeds.iter().any(|&(start, len)| (start..start + len).contains(&82)));
+        assert!(seeds.iter().all(|&(start, len)| almanac.chain().map_source(start + len - 1) == 46));
+        // two ranges onto the same destinations have no inverse
+        let almanac = read("seeds: 1 2\n\na-to-b map:\n0 5 5\n", "a", "b").unwrap();
+        assert_eq!(almanac.chain().composed().invert(), None);
+        assert_eq!(almanac.chain().preimage_ranges((0, 3)), vec![(0, 3), (5, 3)]);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        // line 4 maps 5..15 and line 5 maps 10..20, they share 10..15
+        let text = "seeds: 1 2\n\na-to-b map:\n100 5 10\n200 10 10\n";
+        let e = overlap_in(text, OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (5, "lines 4 and 5 both map sources 10..15"));
+        assert_eq!(e.text, "200 10 10");
+        let first = overlap_in(text, OverlapPolicy::FirstWins).unwrap();
+        let mapped: Vec<u64> = [9, 12, 15, 19, 20].iter().map(|&n| first.chain().map_source(n)).collect();
+        assert_eq!(mapped, vec![104, 107, 205, 209, 20]);
+        let last = overlap_in(text, OverlapPolicy::LastWins).unwrap();
+        let mapped: Vec<u64> = [9, 12, 15, 19, 20].iter().map(|&n| last.chain().map_source(n)).collect();
+        assert_eq!(mapped, vec![104, 202, 205, 209, 20]);
+
+        // a rule inside another one, and two rules onto the same destinations
+        let text = "seeds: 1 2\n\na-to-b map:\n50 0 10\n0 3 2\n60 20 5\n";
+        let e = overlap_in(text, OverlapPolicy::Error).unwrap_err();
+        assert_eq!(e.message, "lines 4 and 5 both map sources 3..5");
+        let text = "seeds: 1 2\n\na-to-b map:\n50 0 10\n55 20 10\n";
+        let e = overlap_in(text, OverlapPolicy::Error).unwrap_err();
+        assert_eq!(e.message, "lines 4 and 5 both map destinations 55..60");
+        assert_eq!((e.line, e.text.as_str()), (5, "55 20 10"));
+        let last = overlap_in(text, OverlapPolicy::LastWins).unwrap();
+        assert_eq!(last.chain().map_source(25), 60);
+        assert_eq!(last.chain().composed().invert(), None);
+    }
+
+    #[test]
+    fn test_unmapped_sources() {
+        // no line maps seed 7, so it stays 7. Pairing the source gap 5.. with
+        // the destination gap 0..10 would send it to 2
+        let text = "seeds: 7 1\n\na-to-b map:\n10 0 5\n";
+        let almanac = read(text, "a", "b").unwrap();
+        assert_eq!((almanac.part1(), almanac.part2()), (7, 7));
+        assert_eq!(almanac.chain().map_source(3), 13);
+        assert_eq!(almanac.chain().map_ranges(&[(3, 4)]), vec![(13, 2), (5, 2)]);
+    }
+
+    fn overlap_in(text: &str, policy: OverlapPolicy) -> Result<Almanac, common::ParseError> {
+        Almanac::read_between(&mut text.as_bytes(), "a", "b", policy)
+    }
+
+    #[test]
+    fn test_broken_pipelines() {
+        let cycle = "seeds: 1 2\n\na-to-b map:\n0 0 1\n\nb-to-c map:\n0 0 1\n\nc-to-a map:\n0 0 1\n";
+        let e = Almanac::read_between(&mut cycle.as_bytes(), "a", "c", OverlapPolicy::Error).unwrap_err();
+        assert_eq!(e.line, 9);
+        assert!(e.message.contains("a -> b -> c -> a"), "{}", e);
+        let twice = "seeds: 1 2\n\na-to-b map:\n0 0 1\n\na-to-b map:\n0 0 1\n";
+        let e = Almanac::read_between(&mut twice.as_bytes(), "a", "b", OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (6, "the map is already on line 3"));
+        assert!(read("seeds: 1 2\n\nnot a map:\n", "a", "b").is_err());
+        // the last map into a category nothing leaves is the one to blame
+        let e = Almanac::read_between(&mut EXAMPLE.as_bytes(), "seed", "color", OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (31, "humidity-to-location map:"));
+        let e = Almanac::read_between(&mut cycle.as_bytes(), "b", "d", OverlapPolicy::Error).unwrap_err();
+        assert!(e.message.contains("the maps go round"), "{}", e);
+        let e = overlap_in("seeds: 1 2\n\nb-to-c map:\n0 0 1\n", OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (4, "no map leads from a to b, nothing maps a"));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = u64::MAX;
+        let e = overlap_in(&format!("seeds: 1 2\n\na-to-b map:\n0 {} 2\n", max), OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (4, "the sources run past u64::MAX"));
+        let e = overlap_in(&format!("seeds: 1 2\n\na-to-b map:\n{} 0 2\n", max), OverlapPolicy::FirstWins).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (4, "the destinations run past u64::MAX"));
+        let e = overlap_in(&format!("seeds: {} 1\n\na-to-b map:\n0 0 1\n", max), OverlapPolicy::Error).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (1, "the seed ranges run past u64::MAX"));
+        // right up to u64::MAX is fine
+        let almanac = overlap_in(&format!("seeds: {} 1\n\na-to-b map:\n0 {} 1\n", max - 1, max - 1), OverlapPolicy::Error).unwrap();
+        assert_eq!((almanac.part1(), almanac.part2()), (0, 0));
+        assert_eq!(almanac.chain().map_ranges(&[(max - 2, 5)]), vec![(max - 2, 1), (0, 1)]);
+    }
+}
@@ -0,0 +1,157 @@
+// random almanacs, and a seed by seed walk through the maps to check the
+// gap filling of `GardenMapper::build` and the range splitting of part 2
+use common::Rng;
+
+use crate::{Almanac, GardenChain, GardenMapper};
+
+const HEADERS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// `lens` laid out one after another in a random order, with random gaps
+fn place(rng: &mut Rng, lens: &[u64]) -> Vec<u64> {
+    let mut order: Vec<usize> = (0..lens.len()).collect();
+    rng.shuffle(&mut order);
+    let mut starts = vec![0; lens.len()];
+    let mut pos = 0;
+    for idx in order {
+        pos += rng.range(0, 6);
+        starts[idx] = pos;
+        pos += lens[idx];
+    }
+    starts
+}
+
+pub fn generate(rng: &mut Rng) -> String {
+    // the seed ranges are free to overlap
+    let pairs = rng.range(1, 4) as usize;
+    let seeds: Vec<String> = (0..pairs)
+        .map(|_| format!("{} {}", rng.range(0, 40), rng.range(1, 10)))
+        .collect();
+    let mut text = format!("seeds: {}\n", seeds.join(" "));
+    // the maps in any order, the almanac finds its way through them
+    let mut headers = HEADERS;
+    rng.shuffle(&mut headers);
+    for header in headers {
+        text.push_str(&format!("\n{} map:\n", header));
+        let count = rng.range(1, 5) as usize;
+        let lens: Vec<u64> = (0..count).map(|_| rng.range(1, 10)).collect();
+        let srcs = place(rng, &lens);
+        let dsts = place(rng, &lens);
+        for i in 0..count {
+            text.push_str(&format!("{} {} {}\n", dsts[i], srcs[i], lens[i]));
+        }
+    }
+    text
+}
+
+// every seed of the ranges taken through every map line by line
+pub fn brute_force(text: &str) -> (u64, u64) {
+    let mut blocks = text.split("\n\n");
+    let seeds: Vec<u64> = blocks
+        .next()
+        .unwrap()
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let mut blocks: Vec<&str> = blocks.collect();
+    blocks.sort_by_key(|block| HEADERS.iter().position(|h| block.starts_with(h)).unwrap());
+    let maps: Vec<Vec<Vec<u64>>> = blocks
+        .iter()
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()).collect())
+                .collect()
+        })
+        .collect();
+    let location = |seed: u64| {
+        maps.iter().fold(seed, |v, map| {
+            map.iter()
+                .find(|m| v >= m[1] && v < m[1] + m[2])
+                .map_or(v, |m| v - m[1] + m[0])
+        })
+    };
+    let part1 = seeds.iter().map(|&s| location(s)).min().unwrap();
+    let part2 = seeds
+        .chunks(2)
+        .flat_map(|pair| pair[0]..pair[0] + pair[1])
+        .map(location)
+        .min()
+        .unwrap();
+    (part1, part2)
+}
+
+#[test]
+fn test_almanac() {
+    for case in 0..3000 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let almanac = Almanac::read(&mut text.as_bytes()).expect("Invalid generated almanac");
+        assert_eq!(
+            (almanac.part1(), almanac.part2()),
+            brute_force(&text),
+            "case {}:\n{}",
+            case,
+            text
+        );
+    }
+}
+
+#[test]
+fn test_compose() {
+    for case in 0..500 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let almanac = Almanac::read(&mut text.as_bytes()).expect("Invalid generated almanac");
+        let chain = almanac.chain();
+        let stages = chain.stages();
+        // a stage at a time, against every pair folded into one
+        for src in 0..chain.composed().end() + 5 {
+            let expected = stages.iter().fold(src, |v, stage| stage.map_source(v));
+            assert_eq!(chain.map_source(src), expected, "case {} seed {}", case, src);
+        }
+        // composing doesn't care how the stages are grouped
+        let first = GardenChain::new(stages[..3].to_vec());
+        let rest = GardenChain::new(stages[3..].to_vec());
+        assert_eq!(&first.composed().compose(rest.composed()), chain.composed());
+        let pairs = GardenChain::new(vec![stages[0].compose(&stages[1]), stages[2].clone()]);
+        assert_eq!(pairs.composed(), first.composed());
+    }
+}
+
+#[test]
+fn test_preimage() {
+    for case in 0..500 {
+        let mut rng = Rng::new(case);
+        let text = generate(&mut rng);
+        let almanac = Almanac::read(&mut text.as_bytes()).expect("Invalid generated almanac");
+        let chain = almanac.chain();
+        let mut mappers: Vec<&GardenMapper> = chain.stages().iter().collect();
+        mappers.push(chain.composed());
+        for mapper in mappers {
+            let end = mapper.end() + 5;
+            let start = rng.range(0, end);
+            let target = (start, rng.range(0, end));
+            let preimage = mapper.preimage_ranges(target);
+            for src in 0..end + 10 {
+                let dst = mapper.map_source(src);
+                let inside = preimage.iter().any(|&(s, l)| src >= s && src < s + l);
+                assert_eq!(inside, dst >= target.0 && dst < target.0 + target.1, "case {} src {}", case, src);
+            }
+            if let Some(inverse) = mapper.invert() {
+                for src in 0..end {
+                    assert_eq!(inverse.map_source(mapper.map_source(src)), src, "case {}", case);
+                }
+            }
+        }
+    }
+}
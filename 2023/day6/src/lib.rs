@@ -0,0 +1,206 @@
+use common::{error, lines, parse_number, Answer, ParseError, Solution};
+use math::{BigUint, Isqrt};
+use std::ops::{Add, Mul, Shr, Sub};
+
+pub struct Races {
+    times: Vec<String>,
+    distances: Vec<String>,
+}
+
+// the numbers of a "<prefix>: n n n" line, kept as digits of any length
+fn parse_numbers(line: usize, text: &str, prefix: &str) -> Result<Vec<String>, ParseError> {
+    let numbers = text
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::line(line, text, format!("expected \"{}\"", prefix)))?;
+    numbers
+        .split_whitespace()
+        .map(|v| parse_number::<BigUint>(line, text, v).map(|_| v.to_string()))
+        .collect()
+}
+
+impl Races {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let mut lines = lines(input);
+        let (line, text) = lines
+            .next()
+            .ok_or_else(|| ParseError::eof(1, "expected the times"))?;
+        let times = parse_numbers(line, text, "Time:")?;
+        let (line, text) = lines
+            .next()
+            .ok_or_else(|| ParseError::eof(line, "expected the distances"))?;
+        let distances = parse_numbers(line, text, "Distance:")?;
+        if times.len() != distances.len() {
+            return Err(ParseError::line(
+                line,
+                text,
+                format!("expected {} distances, found {}", times.len(), distances.len()),
+            ));
+        }
+        Ok(Self { times, distances })
+    }
+
+    pub fn part1(&self) -> BigUint {
+        self.times
+            .iter()
+            .zip(self.distances.iter())
+            .fold(BigUint::from(1u64), |product, (time, distance)| {
+                product * ways(time, distance)
+            })
+    }
+
+    // the spaces between the digits were a bad kerning, it's one race
+    pub fn part2(&self) -> BigUint {
+        ways(&self.times.concat(), &self.distances.concat())
+    }
+}
+
+// what the solver needs from a number, u128 and `BigUint` have it all
+pub trait RaceNumber:
+    Clone
+    + Ord
+    + From<u64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Shr<u32, Output = Self>
+    + Isqrt
+{
+}
+
+impl<N> RaceNumber for N where
+    N: Clone
+        + Ord
+        + From<u64>
+        + Add<Output = N>
+        + Sub<Output = N>
+        + Mul<Output = N>
+        + Shr<u32, Output = N>
+        + Isqrt
+{
+}
+
+// holding the button for h goes h * (time - h), which beats the distance
+// between the roots of h^2 - time * h + distance. The holds that win are
+// symmetric around time / 2, so the shortest one is all it takes
+pub fn ways_to_win<N: RaceNumber>(time: N, distance: N) -> N {
+    let zero = N::from(0);
+    let square = time.clone() * time.clone();
+    let four_distance = distance.clone() * N::from(4);
+    if square <= four_distance {
+        return zero;
+    }
+    let root = (square - four_distance).isqrt();
+    // the floor of the smaller root, or the hold right after it
+    let mut hold = (time.clone() - root) >> 1;
+    let twice = |hold: &N| hold.clone() + hold.clone();
+    while twice(&hold) <= time {
+        if hold.clone() * (time.clone() - hold.clone()) > distance {
+            return time - twice(&hold) + N::from(1);
+        }
+        hold = hold + N::from(1);
+    }
+    zero
+}
+
+// u128 when the time squared fits, `BigUint` for anything longer
+pub fn ways(time: &str, distance: &str) -> BigUint {
+    match (time.parse::<u64>(), distance.parse::<u64>()) {
+        (Ok(time), Ok(distance)) => ways_to_win(time as u128, distance as u128).into(),
+        _ => ways_to_win(
+            time.parse().expect("Races hold digits"),
+            distance.parse().expect("Races hold digits"),
+        ),
+    }
+}
+
+// past what an answer holds is an error, not a wrong number
+fn to_answer(value: BigUint, part: u32) -> Answer {
+    match u128::try_from(&value).ok().and_then(|v| i128::try_from(v).ok()) {
+        Some(v) => v.into(),
+        None => {
+            error!("Part {} overflowed: {}", part, value);
+            Answer::Unsolved
+        }
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Races;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Races::new(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        to_answer(input.part1(), 1)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        to_answer(input.part2(), 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ways, ways_to_win, Races};
+    use common::Rng;
+    use math::BigUint;
+
+    const EXAMPLE: &str = include_str!("../example_input");
+
+    fn brute_force(time: u128, distance: u128) -> u128 {
+        (0..=time).filter(|hold| hold * (time - hold) > distance).count() as u128
+    }
+
+    #[test]
+    fn test_closed_form() {
+        let races = Races::new(EXAMPLE).unwrap();
+        assert_eq!(races.part1(), BigUint::from(288u64));
+        assert_eq!(races.part2(), BigUint::from(71503u64));
+
+        let mut rng = Rng::new(6);
+        for _ in 0..2000 {
+            let time = rng.range(0, 200) as u128;
+            // around the best distance, where the roots are close or equal
+            let distance = rng.range(0, (time * time / 4 + 3) as u64) as u128;
+            let expected = brute_force(time, distance);
+            assert_eq!(ways_to_win(time, distance), expected, "{} {}", time, distance);
+            let big = ways_to_win(BigUint::from(time), BigUint::from(distance));
+            assert_eq!(big, BigUint::from(expected));
+        }
+        assert_eq!(ways_to_win(4u128, 4), 0);
+        assert_eq!(ways_to_win(4u128, 3), 1);
+        assert_eq!(ways_to_win(3u128, 2), 0);
+    }
+
+    #[test]
+    fn test_long_races() {
+        // a 40 digit time, where only `BigUint` holds the squares. The best
+        // a time of 2k + 1 goes is k * (k + 1), with the holds k and k + 1
+        let k: BigUint = "5000000000000000000000000000000000000000".parse().unwrap();
+        let time = (k.clone() + k.clone() + BigUint::from(1u64)).to_string();
+        let best = k.clone() * (k.clone() + BigUint::from(1u64));
+        assert_eq!(ways(&time, &best.to_string()), BigUint::zero());
+        let distance = (best - BigUint::from(1u64)).to_string();
+        assert_eq!(ways(&time, &distance), BigUint::from(2u64));
+        // every hold but 0 and the whole time goes somewhere
+        assert_eq!(ways(&time, "0"), k.clone() + k.clone());
+        let distance = (k.clone() * k.clone()).to_string();
+        let root = "70710678118654752440".parse::<BigUint>().unwrap();
+        // the roots are k + 1/2 -+ sqrt(k + 1/4), with k = 5 * 10^39, the
+        // holds come in pairs around the middle
+        assert_eq!(ways(&time, &distance), root.clone() + root);
+
+        // the same race on both sides of the switch to `BigUint`
+        let (time, distance) = (u64::MAX as u128, (u64::MAX as u128 / 2).pow(2) / 3);
+        assert_eq!(
+            ways(&time.to_string(), &distance.to_string()),
+            ways_to_win(BigUint::from(time), BigUint::from(distance))
+        );
+        // the kerning keeps the leading zeros, every hold from 1 to time - 1 wins
+        let races = Races::new("Time: 4000000000 0000000000 1\nDistance: 3 0 0\n").unwrap();
+        assert_eq!(races.part2().to_string(), "400000000000000000000");
+    }
+}
@@ -0,0 +1,185 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+
+pub struct Hand {
+    value_part1: u32,
+    value_part2: u32,
+    bid: u32,
+}
+
+impl Hand {
+
+    pub fn new(cards: &str, bid: u32) -> Self {
+        let mut value_part1: u32 = Hand::get_rank1(cards);
+        let mut value_part2: u32 = Hand::get_rank2(cards);
+        let cards_bytes = cards.as_bytes();
+        for i in 0..cards_bytes.len() {
+            value_part1 *= 16;
+            value_part2 *= 16;
+            value_part1 += Hand::card_to_value1(cards_bytes[i]);
+            value_part2 += Hand::card_to_value2(cards_bytes[i]);
+        }
+        Self {
+            value_part1,
+            value_part2,
+            bid
+        }
+    }
+
+    fn get_rank1(cards: &str) -> u32 {
+        let mut repeats: [u8;14] = [0;14];
+        let cards_bytes = cards.as_bytes();
+        for i in 0..cards_bytes.len() {
+            let idx = Hand::card_to_value1(cards_bytes[i]);
+            repeats[idx as usize] += 1;
+        }
+        let mut pattern: [u8; 5] = [0; 5];
+        for repeat in repeats[1..].iter() {
+            if *repeat == 0 {
+                continue;
+            }
+            pattern[*repeat as usize -1] += 1;
+        }
+
+        match pattern {
+            [0, 0, 0, 0, 1] => 7,
+            [1, 0, 0, 1, 0] => 6,
+            [0, 1, 1, 0, 0] => 5,
+            [2, 0, 1, 0, 0] => 4,
+            [1, 2, 0, 0, 0] => 3,
+            [3, 1, 0, 0, 0] => 2,
+            [5, 0, 0, 0, 0] => 1,
+            _ => 0
+        }
+    }
+
+    fn get_rank2(cards: &str) -> u32 {
+        let mut repeats: [u8;14] = [0;14];
+        let cards_bytes = cards.as_bytes();
+        for i in 0..cards_bytes.len() {
+            let idx = Hand::card_to_value2(cards_bytes[i]);
+            repeats[idx as usize] += 1;
+        }
+
+        let j_count = repeats[0];
+        let mut max_count = 0;
+        let mut max_idx = 1;
+        for i in 1..14 {
+            if repeats[i] > max_count {
+                max_count = repeats[i];
+                max_idx = i;
+            }
+        }
+        repeats[max_idx] += j_count;
+
+        let mut pattern: [u8; 5] = [0; 5];
+        for repeat in repeats[1..].iter() {
+            if *repeat == 0 {
+                continue;
+            }
+            pattern[*repeat as usize -1] += 1;
+        }
+        match pattern {
+            [0, 0, 0, 0, 1] => 7,
+            [1, 0, 0, 1, 0] => 6,
+            [0, 1, 1, 0, 0] => 5,
+            [2, 0, 1, 0, 0] => 4,
+            [1, 2, 0, 0, 0] => 3,
+            [3, 1, 0, 0, 0] => 2,
+            [5, 0, 0, 0, 0] => 1,
+            _ => 0
+        }
+    }
+
+    fn card_to_value1(card: u8) -> u32 {
+        match card {
+            b'2' => 1,
+            b'3' => 2,
+            b'4' => 3,
+            b'5' => 4,
+            b'6' => 5,
+            b'7' => 6,
+            b'8' => 7,
+            b'9' => 8,
+            b'T' => 9,
+            b'J' => 10,
+            b'Q' => 11,
+            b'K' => 12,
+            b'A' => 13,
+            _ => 0
+        }
+    }
+
+    fn card_to_value2(card: u8) -> u32 {
+        match card {
+            b'J' => 0,
+            b'2' => 1,
+            b'3' => 2,
+            b'4' => 3,
+            b'5' => 4,
+            b'6' => 5,
+            b'7' => 6,
+            b'8' => 7,
+            b'9' => 8,
+            b'T' => 9,
+            b'Q' => 10,
+            b'K' => 11,
+            b'A' => 12,
+            _ => 0
+        }
+    }
+}
+
+pub fn read_hands(input: &str) -> Result<Vec<Hand>, ParseError> {
+    let mut hands = Vec::<Hand>::new();
+    for (line, text) in lines(input) {
+        let mut iter = text.split_whitespace();
+        let Some(cards) = iter.next() else {
+            continue;
+        };
+        if cards.len() != 5 || !cards.chars().all(|c| "23456789TJQKA".contains(c)) {
+            return Err(ParseError::at(line, text, cards, "expected 5 cards out of 23456789TJQKA"));
+        }
+        let bid = iter
+            .next()
+            .ok_or_else(|| ParseError::new(line, text.len() + 1, "", "expected a bid"))?;
+        let bid: u32 = parse_number(line, text, bid)?;
+        hands.push(Hand::new(cards, bid));
+    }
+    Ok(hands)
+}
+
+fn total_winnings(hands: &[Hand], value: fn(&Hand) -> u32) -> u32 {
+    let mut hands: Vec<&Hand> = hands.iter().collect();
+    hands.sort_by_key(|h| value(h));
+    let mut total: u32 = 0;
+    for i in 0..hands.len() {
+        total += (i as u32 + 1) * hands[i].bid;
+    }
+    total
+}
+
+pub fn part1(hands: &[Hand]) -> u32 {
+    total_winnings(hands, |h| h.value_part1)
+}
+
+pub fn part2(hands: &[Hand]) -> u32 {
+    total_winnings(hands, |h| h.value_part2)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_hands(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
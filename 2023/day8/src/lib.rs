@@ -0,0 +1,333 @@
+use common::{lines, Answer, ParseError, Solution};
+use math::crt;
+use std::collections::{HashMap, VecDeque};
+
+use regex::Regex;
+
+struct LinkInput(String, String, String);
+
+impl LinkInput {
+    fn new(input: &str) -> Option<Self> {
+        let input_reg =
+            Regex::new(r"^\s*(?<name>[\w]{3})\s*=\s*\((?<L>[\w]{3}),\s*(?<R>[\w]{3})\)\s*$")
+                .expect("Invalid input regex");
+        input_reg.captures(input).map(|caps| Self(
+                caps.name("name").unwrap().as_str().to_string(),
+                caps.name("L").unwrap().as_str().to_string(),
+                caps.name("R").unwrap().as_str().to_string(),
+            ))
+    }
+}
+
+struct Node {
+    no: usize,
+    name: String,
+    nexts: [usize; 2],
+    froms: [Vec<usize>; 2],
+}
+
+// steps take to the nearest Z
+#[derive(Debug, Clone, Copy)]
+struct Steps2Z {
+    z_no: usize,    // node no which ends with z
+    inst_no: usize, // instruction no
+    len: u64,       // length to z_no node
+}
+
+pub struct Network {
+    instructions: Vec<usize>,
+    nodes: Vec<Node>,
+    name_map: HashMap<String, usize>,
+}
+
+impl Network {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let mut lines = lines(input);
+
+        // parse instructions
+        let (line, text) = lines
+            .next()
+            .ok_or_else(|| ParseError::eof(1, "expected the instructions"))?;
+        let mut instructions = Vec::with_capacity(text.len());
+        for (idx, c) in text.trim_end().char_indices() {
+            instructions.push(match c {
+                'L' => 0,
+                'R' => 1,
+                _ => {
+                    let found = &text[idx..idx + c.len_utf8()];
+                    return Err(ParseError::at(line, text, found, "expected L or R"));
+                }
+            });
+        }
+        if instructions.is_empty() {
+            return Err(ParseError::line(line, text, "expected L or R"));
+        }
+        if let Some((line, text)) = lines.next() {
+            if !text.trim().is_empty() {
+                return Err(ParseError::line(line, text, "expected a blank line"));
+            }
+        }
+
+        // parse nodes
+        let mut links = Vec::<(usize, &str, LinkInput)>::new();
+        let mut name_map: HashMap<String, usize> = HashMap::new();
+        for (line, text) in lines {
+            if text.trim().is_empty() {
+                continue;
+            }
+            let link = LinkInput::new(text.trim())
+                .ok_or_else(|| ParseError::line(line, text, "expected \"AAA = (BBB, CCC)\""))?;
+            let no = links.len();
+            if name_map.insert(link.0.clone(), no).is_some() {
+                return Err(ParseError::line(line, text, format!("node {} is defined twice", link.0)));
+            }
+            links.push((line, text, link));
+        }
+
+        // report a missing node where it is referenced, right of the '='
+        let find = |line: usize, text: &str, name: &str| {
+            name_map.get(name).copied().ok_or_else(|| {
+                let column = text
+                    .find('(')
+                    .and_then(|open| text[open..].find(name).map(|i| open + i))
+                    .unwrap_or(0);
+                ParseError::new(line, column + 1, name, format!("node {} is not defined", name))
+            })
+        };
+
+        // create nodes with nexts
+        let mut nodes = Vec::<Node>::new();
+        for (line, text, link_input) in links.iter() {
+            let no = *name_map.get(&link_input.0).unwrap();
+            let next_l = find(*line, text, &link_input.1)?;
+            let next_r = find(*line, text, &link_input.2)?;
+            let node = Node {
+                no,
+                name: link_input.0.clone(),
+                nexts: [next_l, next_r],
+                froms: [Vec::new(), Vec::new()],
+            };
+            nodes.push(node);
+        }
+
+        // update froms in nodes
+        for i in 0..nodes.len() {
+            let node = &nodes[i];
+            let no = node.no;
+            let next_l = node.nexts[0];
+            let next_r = node.nexts[1];
+            nodes[next_l].froms[0].push(no);
+            nodes[next_r].froms[1].push(no);
+        }
+
+        Ok(Self {
+            instructions,
+            nodes,
+            name_map,
+        })
+    }
+
+    // None without an AAA node, or when the walk never gets to ZZZ
+    pub fn part1(&self) -> Option<usize> {
+        let instructions = &self.instructions;
+        let nodes = &self.nodes;
+        let mut part1_steps = 0;
+        let mut node = &nodes[*self.name_map.get("AAA")?];
+        // past every pair of node and instruction the walk goes round
+        let limit = nodes.len() * instructions.len();
+        while node.name != "ZZZ" {
+            if part1_steps > limit {
+                return None;
+            }
+            let idx = part1_steps % instructions.len();
+            let instruct = instructions[idx];
+            node = &nodes[node.nexts[instruct]];
+            part1_steps += 1;
+        }
+        Some(part1_steps)
+    }
+
+    pub fn part2(&self) -> Option<u64> {
+        let instructions = &self.instructions;
+        let nodes = &self.nodes;
+        // node_idx, inst_idx
+        let mut steps_table: Vec<Vec<Option<Steps2Z>>> = Vec::with_capacity(nodes.len());
+        let mut b_search = VecDeque::<(usize, usize, Steps2Z)>::new();
+        let mut starts = Vec::<usize>::new();
+        let mut ends = Vec::<usize>::new();
+        for node in nodes {
+            let mut ins = Vec::with_capacity(instructions.len());
+            for _ in 0..instructions.len() {
+                ins.push(None);
+            }
+            steps_table.push(ins);
+            if node.name.ends_with('Z') {
+                for inst_no in 0..instructions.len() {
+                    assert!(node.name.ends_with('Z'));
+                    b_search.push_back((
+                        node.no,
+                        inst_no,
+                        Steps2Z {
+                            z_no: node.no,
+                            inst_no,
+                            len: 0,
+                        },
+                    ))
+                }
+                ends.push(node.no);
+            }
+            if node.name.ends_with('A') {
+                starts.push(node.no);
+            }
+        }
+        if starts.is_empty() {
+            return None;
+        }
+
+        while let Some((no, inst_no, step)) = b_search.pop_front() {
+            let node = &nodes[no];
+            let pre_inst_no = (inst_no + instructions.len() - 1) % instructions.len();
+            let pre_inst = instructions[pre_inst_no];
+            for &pre_no in &node.froms[pre_inst] {
+                let pre_step = steps_table[pre_no][pre_inst_no].take();
+                if let Some(pre_step) = pre_step.filter(|s| s.len <= step.len + 1) {
+                    steps_table[pre_no][pre_inst_no].replace(pre_step);
+                    continue;
+                }
+                let mut pre_step = step;
+                pre_step.len += 1;
+                b_search.push_back((pre_no, pre_inst_no, pre_step));
+                steps_table[pre_no][pre_inst_no].replace(pre_step);
+            }
+        }
+
+        // table from z to z loop; (z_no, inst_no)
+        let mut zloop_table: HashMap<(usize, usize), u64> =
+            HashMap::with_capacity(ends.len() * instructions.len());
+        for &e in ends.iter() {
+            for inst_no in 0..instructions.len() {
+                if let Some(step) = steps_table[e][inst_no] {
+                    let mut inst_table = Vec::with_capacity(instructions.len());
+                    for _ in 0..instructions.len() {
+                        inst_table.push(HashMap::with_capacity(ends.len()));
+                    }
+                    let mut b_search = VecDeque::new();
+                    inst_table[step.inst_no].insert(step.z_no, step.len);
+                    b_search.push_back(step);
+
+                    while let Some(step) = b_search.pop_front() {
+                        if let Some(mut next) = steps_table[step.z_no][step.inst_no] {
+                            next.len += step.len;
+                            if !inst_table[next.inst_no].contains_key(&next.z_no) {
+                                inst_table[next.inst_no].insert(next.z_no, next.len);
+                                b_search.push_back(next);
+                            }
+                        }
+                    }
+                    if inst_table[inst_no].contains_key(&e) {
+                        zloop_table.insert((e, inst_no), inst_table[inst_no][&e]);
+                    }
+                }
+            }
+        }
+
+        // table from a to z in inst; a_no, inst_no, z_no
+        let mut a2z_table: HashMap<usize, Vec<HashMap<usize, u64>>> =
+            HashMap::with_capacity(starts.len());
+        for &a in starts.iter() {
+            let mut inst_table = Vec::with_capacity(instructions.len());
+            for _ in 0..instructions.len() {
+                inst_table.push(HashMap::with_capacity(ends.len()));
+            }
+            let mut b_search = VecDeque::new();
+            if let Some(step) = steps_table[a][0] {
+                inst_table[step.inst_no].insert(step.z_no, step.len);
+                b_search.push_back(step);
+            }
+            while let Some(step) = b_search.pop_front() {
+                if let Some(mut next) = steps_table[step.z_no][step.inst_no] {
+                    next.len += step.len;
+                    if !inst_table[next.inst_no].contains_key(&next.z_no) {
+                        inst_table[next.inst_no].insert(next.z_no, next.len);
+                        b_search.push_back(next);
+                    }
+                }
+            }
+            a2z_table.insert(a, inst_table);
+        }
+
+        let mut part2 = None;
+        for inst_no in 0..instructions.len() {
+            if let Some(count) = find_answer(inst_no, Vec::new(), &starts, &a2z_table, &zloop_table) {
+                if part2.is_none() || part2.unwrap() > count {
+                    part2.replace(count);
+                }
+            }
+        }
+        part2
+    }
+}
+
+fn find_answer(
+    inst_no: usize,
+    start2end: Vec<usize>,
+    starts: &Vec<usize>,
+    a2z_table: &HashMap<usize, Vec<HashMap<usize, u64>>>,
+    zloop_table: &HashMap<(usize, usize), u64>,
+) -> Option<u64> {
+    if start2end.len() == starts.len() {
+        // every start first reaches its end after h steps, then again every
+        // loop of l steps, so the answer is the first t >= max(h) with
+        // t = h (mod l) for all of them
+        let mut congruences = Vec::with_capacity(start2end.len());
+        let mut first = 0;
+        for i in 0..start2end.len() {
+            let a_no = starts[i];
+            let e_no = start2end[i];
+            let &h = a2z_table.get(&a_no).unwrap()[inst_no].get(&e_no).unwrap();
+            let &l = zloop_table.get(&(e_no, inst_no))?;
+            congruences.push((h, l));
+            first = first.max(h);
+        }
+        let (t, period) = crt(congruences)?;
+        if t >= first {
+            return Some(t);
+        }
+        return (first - t)
+            .div_ceil(period)
+            .checked_mul(period)
+            .and_then(|v| v.checked_add(t));
+    }
+
+    let i = start2end.len();
+    let a_no = starts[i];
+    let mut result = None;
+    for &z_no in a2z_table.get(&a_no).unwrap().get(inst_no).unwrap().keys() {
+        let mut new_s2e = start2end.clone();
+        new_s2e.push(z_no);
+        if let Some(r) = find_answer(inst_no, new_s2e, starts, a2z_table, zloop_table) {
+            if result.is_none() || result.unwrap() > r {
+                result.replace(r);
+            }
+        }
+    }
+    result
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Network::new(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.part1().into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        input.part2().into()
+    }
+}
@@ -0,0 +1,51 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+
+pub fn calculate_part1(line: &[i64]) -> (i64, i64) {
+    let n_zero = line.iter().any(|v| *v != 0);
+    if !n_zero {
+        return (0, 0);
+    }
+    let mut new_line = Vec::with_capacity(line.len()-1);
+    for i in 0..line.len()-1 {
+        new_line.push(line[i+1] - line[i]);
+    }
+    let (pre, next) = calculate_part1(&new_line);
+    (line.first().unwrap()-pre, next + line.last().unwrap())
+}
+
+pub fn read_histories(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    lines(input)
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(line, text)| {
+            text.split_whitespace()
+                .map(|s| parse_number(line, text, s))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part1(histories: &[Vec<i64>]) -> i64 {
+    histories.iter().map(|h| calculate_part1(h).1).sum()
+}
+
+pub fn part2(histories: &[Vec<i64>]) -> i64 {
+    histories.iter().map(|h| calculate_part1(h).0).sum()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_histories(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    collections::HashMap,
+    fs,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+pub type BenchFn = fn(&str, usize, usize) -> Result<Samples, ParseError>;
+
+// raw timings of every sample, in stage order
+pub struct Samples {
+    pub stages: [Vec<Duration>; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub max_ns: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u32,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub year: u32,
+    pub warmup: usize,
+    pub samples: usize,
+    pub days: Vec<DayReport>,
+}
+
+pub struct Change {
+    pub day: u32,
+    pub stage: &'static str,
+    pub old: Stats,
+    pub new: Stats,
+}
+
+// parse, part1 and part2 are timed separately, each sample parses afresh
+pub fn measure<S: Solution>(
+    input: &str,
+    warmup: usize,
+    samples: usize,
+) -> Result<Samples, ParseError> {
+    let mut stages: [Vec<Duration>; 3] = Default::default();
+    for round in 0..warmup + samples {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        black_box(S::part1(&parsed));
+        let part1 = start.elapsed();
+        let start = Instant::now();
+        black_box(S::part2(&parsed));
+        let part2 = start.elapsed();
+        if round >= warmup {
+            stages[0].push(parse);
+            stages[1].push(part1);
+            stages[2].push(part2);
+        }
+    }
+    Ok(Samples { stages })
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "No samples to summarize");
+        let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        ns.sort();
+        let len = ns.len();
+        let median = if len.is_multiple_of(2) {
+            (ns[len / 2 - 1] + ns[len / 2]) / 2
+        } else {
+            ns[len / 2]
+        };
+        Self {
+            min_ns: ns[0],
+            median_ns: median,
+            mean_ns: ns.iter().sum::<u64>() / len as u64,
+            max_ns: ns[len - 1],
+        }
+    }
+}
+
+impl DayReport {
+    pub fn new(day: u32, samples: &Samples) -> Self {
+        Self {
+            day,
+            parse: Stats::from_samples(&samples.stages[0]),
+            part1: Stats::from_samples(&samples.stages[1]),
+            part2: Stats::from_samples(&samples.stages[2]),
+        }
+    }
+
+    pub fn stages(&self) -> [(&'static str, Stats); 3] {
+        [
+            (STAGES[0], self.parse),
+            (STAGES[1], self.part1),
+            (STAGES[2], self.part2),
+        ]
+    }
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize the report")
+    }
+
+    // one row per day and stage, the run settings are repeated on every row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("year,warmup,samples,day,stage,min_ns,median_ns,mean_ns,max_ns\n");
+        for day in self.days.iter() {
+            for (stage, stats) in day.stages() {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    self.year,
+                    self.warmup,
+                    self.samples,
+                    day.day,
+                    stage,
+                    stats.min_ns,
+                    stats.median_ns,
+                    stats.mean_ns,
+                    stats.max_ns
+                ));
+            }
+        }
+        csv
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Invalid report: {}", e))
+    }
+
+    pub fn from_csv(text: &str) -> Result<Self, String> {
+        let mut report = Report {
+            year: 0,
+            warmup: 0,
+            samples: 0,
+            days: Vec::new(),
+        };
+        let mut days: Vec<(u32, HashMap<String, Stats>)> = Vec::new();
+        for (idx, line) in text.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = || format!("Invalid report line {}: {}", idx + 1, line);
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if fields.len() != 9 {
+                return Err(invalid());
+            }
+            let number = |i: usize| fields[i].parse::<u64>().map_err(|_| invalid());
+            report.year = number(0)? as u32;
+            report.warmup = number(1)? as usize;
+            report.samples = number(2)? as usize;
+            let day = number(3)? as u32;
+            let stats = Stats {
+                min_ns: number(5)?,
+                median_ns: number(6)?,
+                mean_ns: number(7)?,
+                max_ns: number(8)?,
+            };
+            if days.last().is_none_or(|(d, _)| *d != day) {
+                days.push((day, HashMap::new()));
+            }
+            days.last_mut().unwrap().1.insert(fields[4].to_string(), stats);
+        }
+        for (day, mut stages) in days {
+            let mut take = |stage: &str| {
+                stages
+                    .remove(stage)
+                    .ok_or_else(|| format!("Missing {} of day {}", stage, day))
+            };
+            report.days.push(DayReport {
+                day,
+                parse: take(STAGES[0])?,
+                part1: take(STAGES[1])?,
+                part2: take(STAGES[2])?,
+            });
+        }
+        Ok(report)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let text = if is_csv(path) {
+            self.to_csv()
+        } else {
+            self.to_json()
+        };
+        fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if is_csv(path) {
+            Self::from_csv(&text)
+        } else {
+            Self::from_json(&text)
+        }
+    }
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "csv")
+}
+
+// stages found in both reports, compared on their medians
+pub fn diff(old: &Report, new: &Report) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for new_day in new.days.iter() {
+        let Some(old_day) = old.days.iter().find(|d| d.day == new_day.day) else {
+            continue;
+        };
+        for ((stage, old), (_, new)) in old_day.stages().into_iter().zip(new_day.stages()) {
+            changes.push(Change {
+                day: new_day.day,
+                stage,
+                old,
+                new,
+            });
+        }
+    }
+    changes
+}
+
+impl Change {
+    // relative change of the median in percent, positive means slower
+    pub fn percent(&self) -> f64 {
+        if self.old.median_ns == 0 {
+            return 0.0;
+        }
+        (self.new.median_ns as f64 - self.old.median_ns as f64) / self.old.median_ns as f64 * 100.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{diff, DayReport, Report, Samples, Stats};
+
+    fn report(medians: [u64; 3]) -> Report {
+        let samples = Samples {
+            stages: medians.map(|m| vec![Duration::from_nanos(m)]),
+        };
+        Report {
+            year: 2023,
+            warmup: 1,
+            samples: 1,
+            days: vec![DayReport::new(5, &samples)],
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(
+            stats,
+            Stats {
+                min_ns: 1,
+                median_ns: 2,
+                mean_ns: 2,
+                max_ns: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let report = report([10, 200, 3000]);
+        assert_eq!(Report::from_json(&report.to_json()).unwrap(), report);
+        assert_eq!(Report::from_csv(&report.to_csv()).unwrap(), report);
+    }
+
+    #[test]
+    fn test_diff() {
+        let changes = diff(&report([100, 100, 100]), &report([100, 150, 50]));
+        let percents: Vec<f64> = changes.iter().map(|c| c.percent()).collect();
+        assert_eq!(percents, vec![0.0, 50.0, -50.0]);
+    }
+}
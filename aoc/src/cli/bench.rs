@@ -0,0 +1,151 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc::{
+    bench::{self, DayReport, Report},
+    default_input, lookup,
+    runner::format_duration,
+};
+
+use common::Source;
+
+use super::{find_day, parse_number, read_input, unknown_argument};
+
+pub struct BenchArgs {
+    year: u32,
+    day: Option<u32>,
+    warmup: usize,
+    samples: usize,
+    output: Option<PathBuf>,
+}
+
+pub struct DiffArgs {
+    old: PathBuf,
+    new: PathBuf,
+    threshold: f64,
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut bench_args = BenchArgs {
+        year: 2023,
+        day: None,
+        warmup: 1,
+        samples: 10,
+        output: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => bench_args.year = parse_number(&value()?)?,
+            "--day" => bench_args.day = Some(parse_number(&value()?)?),
+            "--warmup" => bench_args.warmup = parse_number(&value()?)?,
+            "--samples" => bench_args.samples = parse_number(&value()?)?,
+            "--output" => bench_args.output = Some(PathBuf::from(value()?)),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    if bench_args.samples == 0 {
+        return Err("--samples must be at least 1".to_string());
+    }
+    Ok(bench_args)
+}
+
+pub fn parse_diff_args(mut args: impl Iterator<Item = String>) -> Result<DiffArgs, String> {
+    let mut paths = Vec::new();
+    let mut threshold = 5.0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                threshold = parse_number(&value)?;
+            }
+            _ if !arg.starts_with("--") => paths.push(PathBuf::from(arg)),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    let [old, new]: [PathBuf; 2] = paths
+        .try_into()
+        .map_err(|_| "bench-diff takes exactly two reports".to_string())?;
+    Ok(DiffArgs {
+        old,
+        new,
+        threshold,
+    })
+}
+
+fn bench_day(args: &BenchArgs, day: u32) -> Result<DayReport, String> {
+    let day_fns = find_day(args.year, day)?;
+    let text = read_input(&Source::Path(default_input(args.year, day)))?;
+    let samples = (day_fns.bench)(&text, args.warmup, args.samples)
+        .map_err(|e| format!("Day {}: {}", day, e))?;
+    Ok(DayReport::new(day, &samples))
+}
+
+pub fn bench(args: BenchArgs) -> Result<(), String> {
+    let days: Vec<u32> = match args.day {
+        Some(day) => vec![day],
+        None => (1..=25)
+            .filter(|&day| lookup(args.year, day).is_some())
+            .filter(|&day| default_input(args.year, day).exists())
+            .collect(),
+    };
+    let mut report = Report {
+        year: args.year,
+        warmup: args.warmup,
+        samples: args.samples,
+        days: Vec::new(),
+    };
+
+    println!("{:>4}  {:>10}  {:>10}  {:>10}", "Day", "Parse", "Part1", "Part2");
+    for day in days {
+        let day_report = bench_day(&args, day)?;
+        let [parse, part1, part2] = day_report
+            .stages()
+            .map(|(_, stats)| format_duration(Duration::from_nanos(stats.median_ns)));
+        println!("{:>4}  {:>10}  {:>10}  {:>10}", day, parse, part1, part2);
+        report.days.push(day_report);
+    }
+    if let Some(output) = args.output {
+        report.write(&output)?;
+    }
+    Ok(())
+}
+
+pub fn diff(args: DiffArgs) -> Result<(), String> {
+    let old = Report::read(&args.old)?;
+    let new = Report::read(&args.new)?;
+    let changes = bench::diff(&old, &new);
+    if changes.is_empty() {
+        return Err("The reports have no day in common".to_string());
+    }
+
+    let mut regressions = 0;
+    println!(
+        "{:>4}  {:<6}  {:>10}  {:>10}  {:>8}",
+        "Day", "Stage", "Old", "New", "Change"
+    );
+    for change in changes.iter() {
+        let percent = change.percent();
+        let flag = if percent > args.threshold {
+            regressions += 1;
+            "  regression"
+        } else {
+            ""
+        };
+        println!(
+            "{:>4}  {:<6}  {:>10}  {:>10}  {:>+7.1}%{}",
+            change.day,
+            change.stage,
+            format_duration(Duration::from_nanos(change.old.median_ns)),
+            format_duration(Duration::from_nanos(change.new.median_ns)),
+            percent,
+            flag
+        );
+    }
+    println!("{} regression(s) over {}%", regressions, args.threshold);
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::{env, fs};
+
+use aoc::{
+    client::{Client, Fetched, Outcome, BASE_URL},
+    http::{transport, Http},
+    workspace_root,
+};
+use common::{Answer, Source};
+
+use super::{find_day, parse_number, read_input, unknown_argument};
+
+pub struct FetchArgs {
+    year: u32,
+    day: u32,
+    force: bool,
+}
+
+pub struct SubmitArgs {
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: Option<String>,
+}
+
+pub fn parse_fetch_args(mut args: impl Iterator<Item = String>) -> Result<FetchArgs, String> {
+    let (mut year, mut day, mut force) = (2023, None, false);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => year = parse_number(&value()?)?,
+            "--day" => day = Some(parse_number(&value()?)?),
+            "--force" => force = true,
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    let day = day.ok_or_else(|| "fetch requires --day".to_string())?;
+    Ok(FetchArgs { year, day, force })
+}
+
+pub fn parse_submit_args(mut args: impl Iterator<Item = String>) -> Result<SubmitArgs, String> {
+    let (mut year, mut day, mut part, mut answer) = (2023, None, None, None);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => year = parse_number(&value()?)?,
+            "--day" => day = Some(parse_number(&value()?)?),
+            "--part" => {
+                let value: u32 = parse_number(&value()?)?;
+                if value != 1 && value != 2 {
+                    return Err(format!("Invalid part {}", value));
+                }
+                part = Some(value);
+            }
+            _ if !arg.starts_with("--") && answer.is_none() => answer = Some(arg),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(SubmitArgs {
+        year,
+        day: day.ok_or_else(|| "submit requires --day".to_string())?,
+        part: part.ok_or_else(|| "submit requires --part".to_string())?,
+        answer,
+    })
+}
+
+// the session cookie comes from AOC_SESSION, or the .session file at the
+// root of the workspace
+fn client() -> Result<Client<Box<dyn Http>>, String> {
+    let session = match env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => {
+            let path = workspace_root().join(".session");
+            fs::read_to_string(&path).map_err(|_| {
+                format!(
+                    "No session cookie, set AOC_SESSION or write it to {}",
+                    path.display()
+                )
+            })?
+        }
+    };
+    let base_url = env::var("AOC_URL").unwrap_or_else(|_| BASE_URL.to_string());
+    Ok(Client::new(
+        transport(&base_url),
+        &base_url,
+        &session,
+        &workspace_root(),
+    ))
+}
+
+pub fn fetch(args: FetchArgs) -> Result<(), String> {
+    let client = client()?;
+    let path = client.input_path(args.year, args.day);
+    match client.fetch(args.year, args.day, args.force)? {
+        Fetched::Cached => println!(
+            "Already have {}, --force downloads it again",
+            path.display()
+        ),
+        Fetched::Downloaded => println!("Saved {}", path.display()),
+    }
+    Ok(())
+}
+
+pub fn submit(args: SubmitArgs) -> Result<(), String> {
+    let client = client()?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        // solve the part on the cached input
+        None => {
+            let day_fns = find_day(args.year, args.day)?;
+            let text = read_input(&Source::Path(client.input_path(args.year, args.day)))?;
+            let results = (day_fns.solve)(&text, Some(args.part))
+                .map_err(|e| format!("Day {}: {}", args.day, e))?;
+            match &results[0].answer {
+                Answer::Unsolved => {
+                    return Err(format!("Day {} part {} has no answer", args.day, args.part))
+                }
+                answer => answer.to_string(),
+            }
+        }
+    };
+    match client.submit(args.year, args.day, args.part, &answer)? {
+        Outcome::Submitted(verdict) => println!("Submitted {}: {}", answer, verdict),
+        Outcome::Known(verdict) => println!("Not submitted, {} is known to be {}", answer, verdict),
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use aoc::{lookup, runner::Day};
+use common::Source;
+
+pub mod bench;
+pub mod client;
+pub mod new;
+pub mod run;
+
+pub const USAGE: &str = "Usage:
+  aoc run [--year <year>] [--day <day>] [--part <1|2>] [--input <path|->] [--jobs <n>]
+          [--format <text|json>]
+  aoc bench [--year <year>] [--day <day>] [--warmup <n>] [--samples <n>] [--output <report>]
+  aoc bench-diff <old report> <new report> [--threshold <percent>]
+  aoc fetch [--year <year>] --day <day> [--force]
+  aoc submit [--year <year>] --day <day> --part <1|2> [answer]
+  aoc new --year <year> --day <day>
+
+Every command takes -v, -vv or -vvv to write info, debug or trace messages to
+stderr, and `--log <day5,day8,...>` to keep only the messages of those crates
+(`aoc` for the runner). At debug level the runner times parsing and each part.
+Errors, like an answer that overflowed, are written either way.
+
+run: runs a single day when --day is given, otherwise runs every day of the
+year on --jobs threads (AOC_THREADS or the number of cores by default) and
+prints a summary table in day order. The input defaults to
+<year>/day<day>/input, `--input -` reads it from stdin. Days 12, 16 and 22
+split their own work over the --jobs threads when run alone, and keep to
+their one thread when every day runs. `--format json` prints one
+{year, day, part, answer, elapsed_ns} object per line instead, with the
+numbers some days find along the way under \"extras\". Answers past 64
+bits and non-finite floats are strings there, null is a part with no answer.
+
+bench: times parsing, part 1 and part 2 of each day separately, after the
+warmup rounds. Reports ending in .csv are written as CSV, anything else as JSON.
+
+bench-diff: compares the median timings of two reports and flags the stages
+that got slower by more than the threshold (5% by default).
+
+fetch: downloads the puzzle input to <year>/day<day>/input, unless it is
+already there. The session cookie is read from AOC_SESSION or from a .session
+file at the root of the workspace.
+
+submit: submits the answer, or the one the solution gives for the input when
+no answer is given. Verdicts are kept in <year>/day<day>/submissions.json and
+answers they already rule out are not sent again.
+
+new: creates <year>/day<day> from the template in aoc/template, with a parser
+skeleton and an example test, and registers it with the workspace and the
+runner.";
+
+pub fn unknown_argument(arg: &str) -> String {
+    format!("Unknown argument {}\n\n{}", arg, USAGE)
+}
+
+pub fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number '{}'", value))
+}
+
+pub fn read_input(source: &Source) -> Result<String, String> {
+    let name = match source {
+        Source::Path(path) => path.display().to_string(),
+        Source::Stdin => "stdin".to_string(),
+        Source::Text(_) => "the input".to_string(),
+    };
+    source
+        .read_to_string()
+        .map_err(|e| format!("Failed to read {}: {}", name, e))
+}
+
+pub fn find_day(year: u32, day: u32) -> Result<Day, String> {
+    lookup(year, day).ok_or_else(|| format!("No solution for year {} day {}", year, day))
+}
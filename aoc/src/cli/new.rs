@@ -0,0 +1,42 @@
+use aoc::{scaffold, workspace_root};
+
+use super::{parse_number, unknown_argument};
+
+pub struct NewArgs {
+    year: u32,
+    day: u32,
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<NewArgs, String> {
+    let (mut year, mut day) = (None, None);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => year = Some(parse_number(&value()?)?),
+            "--day" => day = Some(parse_number(&value()?)?),
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    Ok(NewArgs {
+        year: year.ok_or_else(|| "new requires --year".to_string())?,
+        day: day.ok_or_else(|| "new requires --day".to_string())?,
+    })
+}
+
+pub fn new(args: NewArgs) -> Result<(), String> {
+    let root = workspace_root();
+    for path in scaffold::generate(&root, args.year, args.day)? {
+        let path = path.strip_prefix(&root).unwrap_or(&path);
+        println!("  {}", path.display());
+    }
+    println!(
+        "Created {}, `aoc fetch --year {} --day {}` downloads its input",
+        scaffold::crate_name(args.year, args.day),
+        args.year,
+        args.day
+    );
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+use aoc::{
+    default_input, lookup,
+    runner::{format_duration, to_json},
+};
+use common::{set_threads, span, Answer, Pool, Source};
+
+use super::{find_day, parse_number, read_input, unknown_argument};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+pub struct RunArgs {
+    year: u32,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    jobs: Option<usize>,
+    format: Format,
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut run_args = RunArgs {
+        year: 2023,
+        day: None,
+        part: None,
+        input: None,
+        jobs: None,
+        format: Format::Text,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => run_args.year = parse_number(&value()?)?,
+            "--day" => run_args.day = Some(parse_number(&value()?)?),
+            "--part" => {
+                let part = parse_number(&value()?)?;
+                if part != 1 && part != 2 {
+                    return Err(format!("Invalid part {}", part));
+                }
+                run_args.part = Some(part);
+            }
+            "--input" => run_args.input = Some(value()?),
+            "--jobs" => run_args.jobs = Some(parse_number(&value()?)?),
+            "--format" => {
+                run_args.format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    format => return Err(format!("Unknown format {}", format)),
+                }
+            }
+            _ => return Err(unknown_argument(&arg)),
+        }
+    }
+    if run_args.day.is_none() && run_args.input.is_some() {
+        return Err("--input requires --day".to_string());
+    }
+    Ok(run_args)
+}
+
+fn run_day(args: &RunArgs, day: u32, input: &Source) -> Result<(), String> {
+    let day_fns = find_day(args.year, day)?;
+    let text = read_input(input)?;
+    let _span = span!("day", day = day);
+    let results =
+        (day_fns.solve)(&text, args.part).map_err(|e| format!("Day {}: {}", day, e))?;
+    // only the answers go to stdout, -vv shows the timings
+    for result in results {
+        match args.format {
+            Format::Text => println!("Day {} Part{}: {}", day, result.part, result.answer),
+            Format::Json => println!("{}", to_json(args.year, day, &result)),
+        }
+    }
+    Ok(())
+}
+
+fn run_all(args: &RunArgs) -> Result<(), String> {
+    let year = args.year;
+    let days: Vec<_> = (1..=25)
+        .filter_map(|day| Some((day, lookup(year, day)?, default_input(year, day))))
+        .filter(|(_, _, path)| path.exists())
+        .collect();
+    if days.is_empty() {
+        return Err(format!("No solutions for year {}", year));
+    }
+
+    // the days are independent, the output keeps their order whichever
+    // finishes first
+    let pool = Pool::default();
+    let start = Instant::now();
+    let results = pool.map(&days, |(day, day_fns, path)| {
+        let text = read_input(&Source::Path(path.clone()))?;
+        let _span = span!("day", day = day);
+        let results =
+            (day_fns.solve)(&text, args.part).map_err(|e| format!("Day {}: {}", day, e))?;
+        Ok((*day, results))
+    });
+    let results = results.into_iter().collect::<Result<Vec<_>, String>>()?;
+    let wall = start.elapsed();
+
+    if args.format == Format::Json {
+        for (day, results) in results.iter() {
+            for result in results {
+                println!("{}", to_json(year, *day, result));
+            }
+        }
+        return Ok(());
+    }
+
+    let mut total = Duration::ZERO;
+    println!("{:>4}  {:<20}  {:<20}  {:>10}", "Day", "Part1", "Part2", "Time");
+    for (day, results) in results {
+        let mut answers = [Answer::Unsolved, Answer::Unsolved];
+        let mut elapsed = Duration::ZERO;
+        for result in results {
+            answers[result.part as usize - 1] = result.answer;
+            elapsed += result.elapsed;
+        }
+        total += elapsed;
+        println!(
+            "{:>4}  {:<20}  {:<20}  {:>10}",
+            day,
+            answers[0],
+            answers[1],
+            format_duration(elapsed)
+        );
+    }
+    println!("{:>4}  {:<20}  {:<20}  {:>10}", "", "", "Total", format_duration(total));
+    println!("{:>4}  {:<20}  {:<20}  {:>10}", "", "", "Wall", format_duration(wall));
+    Ok(())
+}
+
+pub fn run(args: RunArgs) -> Result<(), String> {
+    // the days that split their own work get the same limit
+    if let Some(jobs) = args.jobs {
+        set_threads(jobs);
+    }
+    match args.day {
+        Some(day) => {
+            let input = match &args.input {
+                Some(arg) => Source::from_arg(arg),
+                None => Source::Path(default_input(args.year, day)),
+            };
+            run_day(&args, day, &input)
+        }
+        None => run_all(&args),
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::Http;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // the server wants us to wait before the next guess
+    TooSoon,
+    // the part is already solved, or part 1 isn't yet
+    WrongLevel,
+}
+
+impl Verdict {
+    fn from_page(body: &str) -> Option<Self> {
+        if body.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Some(Self::WrongLevel)
+        } else if body.contains("You gave an answer too recently") {
+            Some(Self::TooSoon)
+        } else if body.contains("That's not the right answer") {
+            if body.contains("your answer is too high") {
+                Some(Self::TooHigh)
+            } else if body.contains("your answer is too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+
+    // the verdicts that won't change when the same answer is sent again
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Correct | Self::TooHigh | Self::TooLow | Self::Wrong
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::Wrong => "wrong",
+            Self::TooSoon => "too soon, wait before submitting again",
+            Self::WrongLevel => "not the current level of the puzzle",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Submitted(Verdict),
+    // worked out from earlier submissions, nothing was sent
+    Known(Verdict),
+}
+
+// talks to the puzzle site, keeping inputs and submissions in the day
+// directories under `root`
+pub struct Client<H> {
+    http: H,
+    base_url: String,
+    session: String,
+    root: PathBuf,
+}
+
+impl<H: Http> Client<H> {
+    pub fn new(http: H, base_url: &str, session: &str, root: &Path) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            root: root.to_path_buf(),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn day_dir(&self, year: u32, day: u32) -> PathBuf {
+        self.root.join(year.to_string()).join(format!("day{}", day))
+    }
+
+    pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.day_dir(year, day).join("input")
+    }
+
+    fn submissions_path(&self, year: u32, day: u32) -> PathBuf {
+        self.day_dir(year, day).join("submissions.json")
+    }
+
+    // downloads the input unless it is already on disk
+    pub fn fetch(&self, year: u32, day: u32, force: bool) -> Result<Fetched, String> {
+        let path = self.input_path(year, day);
+        if !force && path.exists() {
+            return Ok(Fetched::Cached);
+        }
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self.http.get(&url, &self.cookie())?;
+        match response.status {
+            200 => {}
+            400 => return Err("The session cookie was rejected, it may have expired".to_string()),
+            404 => {
+                return Err(format!(
+                    "The input of {} day {} is not available yet",
+                    year, day
+                ))
+            }
+            status => {
+                return Err(format!(
+                    "Failed to fetch {}: status {} {}",
+                    url,
+                    status,
+                    response.body.lines().next().unwrap_or_default()
+                ))
+            }
+        }
+        fs::create_dir_all(self.day_dir(year, day)).map_err(|e| {
+            format!(
+                "Failed to create {}: {}",
+                self.day_dir(year, day).display(),
+                e
+            )
+        })?;
+        fs::write(&path, response.body)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(Fetched::Downloaded)
+    }
+
+    pub fn submissions(&self, year: u32, day: u32) -> Result<Vec<Submission>, String> {
+        let path = self.submissions_path(year, day);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid submissions in {}: {}", path.display(), e))
+    }
+
+    fn record(&self, year: u32, day: u32, submission: Submission) -> Result<(), String> {
+        let mut submissions = self.submissions(year, day)?;
+        submissions.push(submission);
+        let path = self.submissions_path(year, day);
+        fs::create_dir_all(self.day_dir(year, day)).map_err(|e| {
+            format!(
+                "Failed to create {}: {}",
+                self.day_dir(year, day).display(),
+                e
+            )
+        })?;
+        let text = serde_json::to_string_pretty(&submissions)
+            .expect("Failed to serialize the submissions");
+        fs::write(&path, text + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    // only sends answers the earlier verdicts can't already rule on
+    pub fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> Result<Outcome, String> {
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Err("Refusing to submit an empty answer".to_string());
+        }
+        if let Some(verdict) = predict(&self.submissions(year, day)?, part, answer) {
+            return Ok(Outcome::Known(verdict));
+        }
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let form = format!("level={}&answer={}", part, url_encode(answer));
+        let response = self.http.post(&url, &self.cookie(), &form)?;
+        if response.status != 200 {
+            return Err(format!(
+                "Failed to submit to {}: status {}",
+                url, response.status
+            ));
+        }
+        let verdict = Verdict::from_page(&response.body)
+            .ok_or_else(|| format!("Unrecognized response from {}", url))?;
+        if verdict.is_final() {
+            self.record(
+                year,
+                day,
+                Submission {
+                    part,
+                    answer: answer.to_string(),
+                    verdict,
+                },
+            )?;
+        }
+        Ok(Outcome::Submitted(verdict))
+    }
+}
+
+// what the earlier submissions of the part say about `answer`
+fn predict(submissions: &[Submission], part: u32, answer: &str) -> Option<Verdict> {
+    let submissions: Vec<&Submission> = submissions.iter().filter(|s| s.part == part).collect();
+    if let Some(same) = submissions.iter().find(|s| s.answer == answer) {
+        return Some(same.verdict);
+    }
+    let number: Option<i128> = answer.parse().ok();
+    let mut verdict = None;
+    for submission in submissions {
+        let known: Option<i128> = submission.answer.parse().ok();
+        match (submission.verdict, number, known) {
+            (Verdict::Correct, Some(number), Some(known)) if number > known => {
+                return Some(Verdict::TooHigh)
+            }
+            (Verdict::Correct, Some(_), Some(_)) => return Some(Verdict::TooLow),
+            (Verdict::Correct, _, _) => return Some(Verdict::Wrong),
+            (Verdict::TooHigh, Some(number), Some(known)) if number >= known => {
+                verdict = Some(Verdict::TooHigh)
+            }
+            (Verdict::TooLow, Some(number), Some(known)) if number <= known => {
+                verdict = Some(Verdict::TooLow)
+            }
+            _ => {}
+        }
+    }
+    verdict
+}
+
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
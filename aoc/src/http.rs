@@ -0,0 +1,174 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+const USER_AGENT: &str = "aoc-runner";
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// the requests the client makes, so tests can swap the network for a local
+// server
+pub trait Http {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, String>;
+
+    // `form` is already url encoded
+    fn post(&self, url: &str, cookie: &str, form: &str) -> Result<Response, String>;
+}
+
+// plain HTTP/1.1 over a socket, no TLS, good for local servers only
+pub struct TcpHttp;
+
+impl TcpHttp {
+    fn request(
+        &self,
+        method: &str,
+        url: &str,
+        cookie: &str,
+        form: Option<&str>,
+    ) -> Result<Response, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("Only http:// urls are supported, got {}", url))?;
+        let (host, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+        let mut stream = TcpStream::connect(&address)
+            .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+        stream
+            .set_read_timeout(Some(Duration::from_secs(30)))
+            .map_err(|e| e.to_string())?;
+
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: {}\r\nConnection: close\r\n",
+            method, path, host, USER_AGENT, cookie
+        );
+        if let Some(form) = form {
+            request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            request.push_str(&format!("Content-Length: {}\r\n\r\n{}", form.len(), form));
+        } else {
+            request.push_str("\r\n");
+        }
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| format!("Failed to send the request to {}: {}", host, e))?;
+
+        // the server closes the connection after the body
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .map_err(|e| format!("Failed to read the response from {}: {}", host, e))?;
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| format!("Malformed response from {}", host))?;
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| format!("Malformed status line from {}", host))?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+impl Http for TcpHttp {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, String> {
+        self.request("GET", url, cookie, None)
+    }
+
+    fn post(&self, url: &str, cookie: &str, form: &str) -> Result<Response, String> {
+        self.request("POST", url, cookie, Some(form))
+    }
+}
+
+// leaves TLS to the curl binary
+pub struct CurlHttp;
+
+impl CurlHttp {
+    fn request(&self, url: &str, cookie: &str, form: Option<&str>) -> Result<Response, String> {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--user-agent", USER_AGENT])
+            // the cookie header comes from stdin to keep it out of the process list
+            .args(["--header", "@-"])
+            // the status code goes on a line of its own after the body
+            .args(["--write-out", "\n%{http_code}"]);
+        if let Some(form) = form {
+            command.args(["--data", form]);
+        }
+        let mut child = command
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        let mut stdin = child.stdin.take().unwrap();
+        stdin
+            .write_all(format!("Cookie: {}\n", cookie).as_bytes())
+            .map_err(|e| format!("Failed to pass the cookie to curl: {}", e))?;
+        drop(stdin);
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let output = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = output
+            .rsplit_once('\n')
+            .ok_or_else(|| "Malformed curl output".to_string())?;
+        let status = status
+            .parse()
+            .map_err(|_| format!("Invalid status code {}", status))?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+impl Http for CurlHttp {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, String> {
+        self.request(url, cookie, None)
+    }
+
+    fn post(&self, url: &str, cookie: &str, form: &str) -> Result<Response, String> {
+        self.request(url, cookie, Some(form))
+    }
+}
+
+// https goes through curl, plain http through our own socket
+pub fn transport(base_url: &str) -> Box<dyn Http> {
+    if base_url.starts_with("http://") {
+        Box::new(TcpHttp)
+    } else {
+        Box::new(CurlHttp)
+    }
+}
+
+impl<H: Http + ?Sized> Http for Box<H> {
+    fn get(&self, url: &str, cookie: &str) -> Result<Response, String> {
+        (**self).get(url, cookie)
+    }
+
+    fn post(&self, url: &str, cookie: &str, form: &str) -> Result<Response, String> {
+        (**self).post(url, cookie, form)
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::{Path, PathBuf};
+
+pub mod bench;
+pub mod client;
+pub mod http;
+pub mod runner;
+pub mod scaffold;
+pub mod year2023;
+
+use runner::Day;
+
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+pub fn day_dir(year: u32, day: u32) -> PathBuf {
+    workspace_root()
+        .join(year.to_string())
+        .join(format!("day{}", day))
+}
+
+pub fn default_input(year: u32, day: u32) -> PathBuf {
+    day_dir(year, day).join("input")
+}
+
+pub fn lookup(year: u32, day: u32) -> Option<Day> {
+    match year {
+        2023 => year2023::day(day),
+        _ => None,
+    }
+}
@@ -0,0 +1,30 @@
+use std::{env, process};
+
+mod cli;
+
+use cli::USAGE;
+use common::log::{self, Filter};
+
+fn main() {
+    // -v and --log are taken out wherever they are
+    let (filter, args) = Filter::from_args(env::args().skip(1));
+    log::init(filter);
+    let mut args = args.into_iter();
+    let result = match args.next().as_deref() {
+        Some("run") => cli::run::parse_args(args).and_then(cli::run::run),
+        Some("bench") => cli::bench::parse_args(args).and_then(cli::bench::bench),
+        Some("bench-diff") => cli::bench::parse_diff_args(args).and_then(cli::bench::diff),
+        Some("fetch") => cli::client::parse_fetch_args(args).and_then(cli::client::fetch),
+        Some("submit") => cli::client::parse_submit_args(args).and_then(cli::client::submit),
+        Some("new") => cli::new::parse_args(args).and_then(cli::new::new),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use common::{
+    extras::{self, Extra},
+    span, Answer, ParseError, Solution,
+};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::bench::{self, BenchFn};
+
+pub struct PartResult {
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub extras: Vec<Extra>,
+}
+
+pub type SolveFn = fn(&str, Option<u32>) -> Result<Vec<PartResult>, ParseError>;
+
+// everything the tooling can do with one day, monomorphized per solution
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub solve: SolveFn,
+    pub bench: BenchFn,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            solve: solve::<S>,
+            bench: bench::measure::<S>,
+        }
+    }
+}
+
+// run the selected part, or both parts when `part` is None
+pub fn solve<S: Solution>(input: &str, part: Option<u32>) -> Result<Vec<PartResult>, ParseError> {
+    let input = {
+        let _span = span!("parse");
+        S::parse(input)?
+    };
+    let mut results = Vec::with_capacity(2);
+    if part.is_none_or(|p| p == 1) {
+        let _span = span!("part1");
+        let start = Instant::now();
+        let (answer, extras) = extras::collect(|| S::part1(&input));
+        results.push(PartResult {
+            part: 1,
+            answer,
+            elapsed: start.elapsed(),
+            extras,
+        });
+    }
+    if part.is_none_or(|p| p == 2) {
+        let _span = span!("part2");
+        let start = Instant::now();
+        let (answer, extras) = extras::collect(|| S::part2(&input));
+        results.push(PartResult {
+            part: 2,
+            answer,
+            elapsed: start.elapsed(),
+            extras,
+        });
+    }
+    Ok(results)
+}
+
+pub fn format_duration(elapsed: Duration) -> String {
+    let micros = elapsed.as_micros();
+    if micros < 1000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", micros as f64 / 1_000_000.0)
+    }
+}
+
+fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        // past 64 bits a string, so nothing gets rounded
+        Answer::Int(v) => i64::try_from(*v)
+            .map(Value::from)
+            .or_else(|_| u64::try_from(*v).map(Value::from))
+            .unwrap_or_else(|_| Value::from(v.to_string())),
+        // JSON has no NaN or infinity, null would read as a missing part
+        Answer::Float(v) if !v.is_finite() => Value::from(v.to_string()),
+        Answer::Float(v) => Value::from(*v),
+        Answer::Unsolved => Value::Null,
+    }
+}
+
+#[derive(Serialize)]
+struct Record {
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: Value,
+    elapsed_ns: u64,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    extras: Map<String, Value>,
+}
+
+// one line of `--format json`, null answers are parts the day doesn't have
+pub fn to_json(year: u32, day: u32, result: &PartResult) -> String {
+    let extras = result
+        .extras
+        .iter()
+        .map(|extra| {
+            let values = extra.values.iter().map(answer_value).collect();
+            (extra.name.to_string(), Value::Array(values))
+        })
+        .collect();
+    let record = Record {
+        year,
+        day,
+        part: result.part,
+        answer: answer_value(&result.answer),
+        elapsed_ns: result.elapsed.as_nanos() as u64,
+        extras,
+    };
+    serde_json::to_string(&record).expect("Failed to serialize the answer")
+}
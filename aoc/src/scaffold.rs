@@ -0,0 +1,142 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TEMPLATE: &str = include_str!("../template/Cargo.toml");
+const LIB_TEMPLATE: &str = include_str!("../template/lib.rs");
+const MAIN_TEMPLATE: &str = include_str!("../template/main.rs");
+const YEAR_TEMPLATE: &str = include_str!("../template/year.rs");
+
+// 2023 predates the generator, its crates are plain `day<day>`
+pub fn crate_name(year: u32, day: u32) -> String {
+    format!("day{}-{}", day, year)
+}
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    let name = crate_name(year, day);
+    template
+        .replace("__CRATE__", &name)
+        .replace("__NAME__", &name.replace('-', "_"))
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    fs::write(path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// puts `line` in front of the first line of `text` that starts with `anchor`
+fn insert_before(text: &str, anchor: &str, line: &str, path: &Path) -> Result<String, String> {
+    let idx = text
+        .lines()
+        .scan(0, |offset, l| {
+            let start = *offset;
+            *offset += l.len() + 1;
+            Some((start, l))
+        })
+        .find(|(_, l)| l.starts_with(anchor))
+        .map(|(start, _)| start)
+        .ok_or_else(|| format!("Can't find '{}' in {}", anchor.trim(), path.display()))?;
+    Ok(format!("{}{}\n{}", &text[..idx], line, &text[idx..]))
+}
+
+// creates <year>/day<day> under `root` from the template and registers it
+// with the workspace and the runner, returns the files it touched
+pub fn generate(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day {}", day));
+    }
+    if year < 2015 {
+        return Err(format!("Invalid year {}", year));
+    }
+    let dir = root.join(year.to_string()).join(format!("day{}", day));
+    if dir.join("Cargo.toml").exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let mut touched = Vec::new();
+    for (file, template) in [
+        ("Cargo.toml", CARGO_TEMPLATE),
+        ("src/lib.rs", LIB_TEMPLATE),
+        ("src/main.rs", MAIN_TEMPLATE),
+        ("example_input", ""),
+    ] {
+        let path = dir.join(file);
+        write(&path, &fill(template, year, day))?;
+        touched.push(path);
+    }
+
+    // the workspace picks up every day of the year
+    let path = root.join("Cargo.toml");
+    let text = read(&path)?;
+    let members = format!("\"{}/day*\"", year);
+    if !text.contains(&members) {
+        let line = text
+            .lines()
+            .find(|l| l.starts_with("members = ["))
+            .ok_or_else(|| format!("Can't find the members in {}", path.display()))?;
+        let updated = line.replacen("]", &format!(", {}]", members), 1);
+        write(&path, &text.replacen(line, &updated, 1))?;
+        touched.push(path);
+    }
+
+    let name = crate_name(year, day);
+    let path = root.join("aoc/Cargo.toml");
+    let mut text = read(&path)?;
+    // the dependencies end with a blank line before the lints
+    let idx = text.find("\n\n[lints]").ok_or_else(|| {
+        format!(
+            "Can't find the end of the dependencies in {}",
+            path.display()
+        )
+    })?;
+    text.insert_str(
+        idx,
+        &format!("\n{} = {{ path = \"../{}/day{}\" }}", name, year, day),
+    );
+    write(&path, &text)?;
+    touched.push(path);
+
+    // one module per year maps the days to their solutions
+    let path = root.join(format!("aoc/src/year{}.rs", year));
+    let text = if path.exists() {
+        read(&path)?
+    } else {
+        YEAR_TEMPLATE.to_string()
+    };
+    let entry = format!(
+        "    ({}, Day::of::<{}::Solver>()),",
+        day,
+        name.replace('-', "_")
+    );
+    write(&path, &insert_before(&text, "];", &entry, &path)?)?;
+    touched.push(path);
+
+    let path = root.join("aoc/src/lib.rs");
+    let mut text = read(&path)?;
+    let module = format!("pub mod year{};", year);
+    if !text.contains(&module) {
+        let idx = text
+            .rfind("pub mod year")
+            .and_then(|idx| text[idx..].find('\n').map(|end| idx + end + 1))
+            .ok_or_else(|| format!("Can't find the year modules in {}", path.display()))?;
+        text.insert_str(idx, &format!("{}\n", module));
+        text = insert_before(
+            &text,
+            "        _ => None,",
+            &format!("        {} => year{}::day(day),", year, year),
+            &path,
+        )?;
+        write(&path, &text)?;
+        touched.push(path);
+    }
+    Ok(touched)
+}
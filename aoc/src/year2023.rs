@@ -0,0 +1,34 @@
+use crate::runner::Day;
+
+const DAYS: [Day; 25] = [
+    Day::of::<day1::Solver>(),
+    Day::of::<day2::Solver>(),
+    Day::of::<day3::Solver>(),
+    Day::of::<day4::Solver>(),
+    Day::of::<day5::Solver>(),
+    Day::of::<day6::Solver>(),
+    Day::of::<day7::Solver>(),
+    Day::of::<day8::Solver>(),
+    Day::of::<day9::Solver>(),
+    Day::of::<day10::Solver>(),
+    Day::of::<day11::Solver>(),
+    Day::of::<day12::Solver>(),
+    Day::of::<day13::Solver>(),
+    Day::of::<day14::Solver>(),
+    Day::of::<day15::Solver>(),
+    Day::of::<day16::Solver>(),
+    Day::of::<day17::Solver>(),
+    Day::of::<day18::Solver>(),
+    Day::of::<day19::Solver>(),
+    Day::of::<day20::Solver>(),
+    Day::of::<day21::Solver>(),
+    Day::of::<day22::Solver>(),
+    Day::of::<day23::Solver>(),
+    Day::of::<day24::Solver>(),
+    Day::of::<day25::Solver>(),
+];
+
+pub fn day(day: u32) -> Option<Day> {
+    let idx = (day as usize).checked_sub(1)?;
+    DAYS.get(idx).copied()
+}
@@ -0,0 +1,62 @@
+use common::{lines, parse_number, Answer, ParseError, Solution};
+
+// __YEAR__ day __DAY__
+pub struct Puzzle {
+    pub rows: Vec<Vec<i64>>,
+}
+
+pub fn read_puzzle(input: &str) -> Result<Puzzle, ParseError> {
+    let mut rows = Vec::new();
+    for (line, text) in lines(input) {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let mut row = Vec::new();
+        for field in text.split_whitespace() {
+            row.push(parse_number(line, text, field)?);
+        }
+        rows.push(row);
+    }
+    Ok(Puzzle { rows })
+}
+
+pub fn part1(_puzzle: &Puzzle) -> Option<i64> {
+    None
+}
+
+pub fn part2(_puzzle: &Puzzle) -> Option<i64> {
+    None
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_puzzle(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+
+    use super::Solver;
+
+    // `-` until the part is solved
+    #[test]
+    fn example() {
+        let input = Solver::parse(include_str!("../example_input")).expect("Failed to parse the example");
+        assert_eq!(Solver::part1(&input).to_string(), "-");
+        assert_eq!(Solver::part2(&input).to_string(), "-");
+    }
+}
@@ -0,0 +1,10 @@
+use common::{Solution, Source};
+use __NAME__::Solver;
+
+fn main() {
+    let source = Source::from_args(concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+    let text = source.read_to_string().expect("Failed to read input file.");
+    let input = Solver::parse(&text).expect("Failed to parse input file.");
+    println!("Part1 {}", Solver::part1(&input));
+    println!("Part2 {}", Solver::part2(&input));
+}
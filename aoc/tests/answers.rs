@@ -0,0 +1,83 @@
+use std::fs;
+
+use aoc::{day_dir, lookup};
+
+const YEAR: u32 = 2023;
+
+struct Expected {
+    day: u32,
+    input: String,
+    answers: [Option<String>; 2],
+}
+
+// every day keeps an `answers` manifest next to its inputs, one line per
+// input file: `<file> <part1> <part2>`, with `-` for parts not checked
+fn read_manifests() -> Vec<Expected> {
+    let mut expected = Vec::new();
+    for day in 1..=25 {
+        let path = day_dir(YEAR, day).join("answers");
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(fields.len(), 3, "Invalid line in {}: {}", path.display(), line);
+            let answer = |s: &str| (s != "-").then(|| s.to_string());
+            expected.push(Expected {
+                day,
+                input: fields[0].to_string(),
+                answers: [answer(fields[1]), answer(fields[2])],
+            });
+        }
+    }
+    expected
+}
+
+fn check(filter: impl Fn(&Expected) -> bool) {
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    for expected in read_manifests().iter().filter(|e| filter(e)) {
+        let path = day_dir(YEAR, expected.day).join(&expected.input);
+        let text = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+        let solve = lookup(YEAR, expected.day)
+            .expect("No solution for the day")
+            .solve;
+        for (idx, answer) in expected.answers.iter().enumerate() {
+            let Some(answer) = answer else {
+                continue;
+            };
+            let part = idx as u32 + 1;
+            checked += 1;
+            let actual = match solve(&text, Some(part)) {
+                Ok(results) => results[0].answer.to_string(),
+                Err(e) => e.to_string(),
+            };
+            if actual != *answer {
+                failures.push(format!(
+                    "day{} {} part{}: expected {}, got {}",
+                    expected.day, expected.input, part, answer, actual
+                ));
+            }
+        }
+    }
+    assert!(checked > 0, "No answers checked");
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn example_answers() {
+    check(|e| e.input != "input");
+}
+
+// some days take seconds even in release, run with
+// `cargo test --release -- --ignored`
+#[test]
+#[ignore]
+fn input_answers() {
+    check(|e| e.input == "input");
+}
@@ -0,0 +1,215 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use aoc::{
+    client::{Client, Fetched, Outcome, Verdict},
+    http::TcpHttp,
+};
+
+const SESSION: &str = "0123abcd";
+const INPUT: &str = "1abc2\npqr3stu8vwx\n";
+
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    cookie: String,
+    body: String,
+}
+
+// stands in for the puzzle site on a local port, part 1 of 2023 day 1 is 142
+struct StandIn {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StandIn {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.expect("Failed to accept");
+                let request = read_request(&mut BufReader::new(&mut stream));
+                let (status, body) = respond(&request);
+                log.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Request {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut fields = line.split_whitespace();
+    let method = fields.next().unwrap().to_string();
+    let path = fields.next().unwrap().to_string();
+    let (mut cookie, mut length) = (String::new(), 0);
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(": ").unwrap();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = value.to_string(),
+            "content-length" => length = value.parse().unwrap(),
+            _ => {}
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).unwrap(),
+    }
+}
+
+fn respond(request: &Request) -> (u16, String) {
+    if request.cookie != format!("session={}", SESSION) {
+        return (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string(),
+        );
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/2023/day/1/input") => (200, INPUT.to_string()),
+        ("POST", "/2023/day/1/answer") => {
+            let form: Vec<(&str, &str)> = request
+                .body
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .collect();
+            let page = match form.as_slice() {
+                [("level", "2"), _] => "You don't seem to be solving the right level.",
+                [("level", "1"), ("answer", "soon")] => "You gave an answer too recently.",
+                [("level", "1"), ("answer", "142")] => "That's the right answer!",
+                [("level", "1"), ("answer", answer)] => match answer.parse::<u64>() {
+                    Ok(v) if v > 142 => "That's not the right answer; your answer is too high.",
+                    Ok(_) => "That's not the right answer; your answer is too low.",
+                    Err(_) => "That's not the right answer.",
+                },
+                _ => return (400, "Bad form".to_string()),
+            };
+            (
+                200,
+                format!("<main><article><p>{}</p></article></main>", page),
+            )
+        }
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn fetch_input() {
+    let server = StandIn::start();
+    let root = scratch_dir("fetch");
+    let client = Client::new(TcpHttp, &server.url, SESSION, &root);
+
+    assert_eq!(client.fetch(2023, 1, false), Ok(Fetched::Downloaded));
+    assert_eq!(
+        fs::read_to_string(root.join("2023/day1/input")).unwrap(),
+        INPUT
+    );
+    // cached from now on, unless forced
+    assert_eq!(client.fetch(2023, 1, false), Ok(Fetched::Cached));
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(client.fetch(2023, 1, true), Ok(Fetched::Downloaded));
+    assert_eq!(server.requests().len(), 2);
+    assert!(server
+        .requests()
+        .iter()
+        .all(|r| r.cookie == "session=0123abcd"));
+
+    assert!(client
+        .fetch(2023, 2, false)
+        .unwrap_err()
+        .contains("not available"));
+    let rejected = Client::new(TcpHttp, &server.url, "expired", &root);
+    assert!(rejected
+        .fetch(2023, 1, true)
+        .unwrap_err()
+        .contains("cookie"));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn submit_answers() {
+    let server = StandIn::start();
+    let root = scratch_dir("submit");
+    let client = Client::new(TcpHttp, &server.url, SESSION, &root);
+    let submit = |part, answer| client.submit(2023, 1, part, answer).unwrap();
+
+    assert_eq!(submit(2, "1"), Outcome::Submitted(Verdict::WrongLevel));
+    assert_eq!(submit(1, "soon"), Outcome::Submitted(Verdict::TooSoon));
+    assert_eq!(submit(1, "200"), Outcome::Submitted(Verdict::TooHigh));
+    assert_eq!(submit(1, "100"), Outcome::Submitted(Verdict::TooLow));
+    assert_eq!(server.requests().len(), 4);
+    assert_eq!(server.requests()[3].body, "level=1&answer=100");
+
+    // the same guesses, or ones the bounds already rule out, are not sent
+    assert_eq!(submit(1, "200"), Outcome::Known(Verdict::TooHigh));
+    assert_eq!(submit(1, "300"), Outcome::Known(Verdict::TooHigh));
+    assert_eq!(submit(1, "99"), Outcome::Known(Verdict::TooLow));
+    assert_eq!(server.requests().len(), 4);
+
+    assert_eq!(submit(1, "142"), Outcome::Submitted(Verdict::Correct));
+    assert_eq!(submit(1, "141"), Outcome::Known(Verdict::TooLow));
+    assert_eq!(submit(1, "abc"), Outcome::Known(Verdict::Wrong));
+    assert_eq!(server.requests().len(), 5);
+
+    // only the verdicts that hold for good are kept
+    let verdicts: Vec<(u32, String, Verdict)> = client
+        .submissions(2023, 1)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.part, s.answer, s.verdict))
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![
+            (1, "200".to_string(), Verdict::TooHigh),
+            (1, "100".to_string(), Verdict::TooLow),
+            (1, "142".to_string(), Verdict::Correct),
+        ]
+    );
+    // and they survive a new client
+    let client = Client::new(TcpHttp, &server.url, SESSION, &root);
+    assert_eq!(
+        client.submit(2023, 1, 1, "142"),
+        Ok(Outcome::Known(Verdict::Correct))
+    );
+    assert_eq!(server.requests().len(), 5);
+    fs::remove_dir_all(root).unwrap();
+}
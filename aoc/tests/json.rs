@@ -0,0 +1,54 @@
+use std::{fs, time::Duration};
+
+use aoc::{
+    day_dir,
+    runner::{solve, to_json, PartResult},
+};
+use common::{extras::Extra, Answer};
+use serde_json::{json, Value};
+
+fn parse(line: &str) -> Value {
+    serde_json::from_str(line).expect("Failed to parse the record")
+}
+
+#[test]
+fn answer_records() {
+    let text = fs::read_to_string(day_dir(2023, 25).join("example_input"))
+        .expect("Failed to read the example");
+    let results = solve::<day25::Solver>(&text, None).expect("Failed to solve the example");
+    let records: Vec<Value> = results.iter().map(|r| parse(&to_json(2023, 25, r))).collect();
+    assert_eq!(records[0]["answer"], json!(54));
+    assert_eq!(records[1]["answer"], Value::Null);
+    for (part, record) in records.iter().enumerate() {
+        assert_eq!(record["year"], json!(2023));
+        assert_eq!(record["day"], json!(25));
+        assert_eq!(record["part"], json!(part + 1));
+        assert!(record["elapsed_ns"].is_u64());
+        assert!(record.get("extras").is_none());
+    }
+
+    let result = PartResult {
+        part: 2,
+        answer: Answer::Int(1 << 80),
+        elapsed: Duration::from_nanos(1500),
+        extras: vec![Extra {
+            name: "quadratic",
+            values: vec![Answer::Int(3), Answer::Float(0.5), Answer::Int(-1)],
+        }],
+    };
+    assert_eq!(
+        to_json(2023, 21, &result),
+        r#"{"year":2023,"day":21,"part":2,"answer":"1208925819614629174706176","elapsed_ns":1500,"extras":{"quadratic":[3,0.5,-1]}}"#
+    );
+    // JSON has no non-finite numbers, they go out as strings rather than null
+    let floats = [(f64::NAN, "NaN"), (f64::INFINITY, "inf"), (f64::NEG_INFINITY, "-inf")];
+    for (float, answer) in floats {
+        let result = PartResult {
+            part: 1,
+            answer: Answer::Float(float),
+            elapsed: Duration::ZERO,
+            extras: Vec::new(),
+        };
+        assert_eq!(parse(&to_json(2023, 24, &result))["answer"], json!(answer));
+    }
+}
@@ -0,0 +1,93 @@
+use aoc::lookup;
+
+const YEAR: u32 = 2023;
+
+// malformed inputs, and the line and column each error has to point at.
+// Day 1 takes any text, lines without digits just count for nothing
+const CASES: [(u32, &str, usize, usize); 32] = [
+    (2, "Game 1: 3 blue, 4 red\nGame 2: 1 red, 2 purple\n", 2, 16),
+    (3, "467..114..\n...*......\n..35..\n", 3, 7),
+    (4, "Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30\n", 2, 12),
+    (5, "seeds: 79 14 55\n", 1, 1),
+    (6, "Time:      7  15   30\nDistance:  9  40\n", 2, 1),
+    (7, "32T3K 765\nT55J5\n", 2, 6),
+    (8, "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\n", 4, 13),
+    (9, "0 3 6 9\n1 3 six 10\n", 2, 5),
+    (10, "-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JX\n", 5, 5),
+    (11, "...#\n..#.\n.#x.\n", 3, 3),
+    (12, "???.### 1,1,3\n.??..??...?##. 1,a,3\n", 2, 18),
+    (13, "#.##..##.\n..#.##.#.\n##.....X.\n", 3, 8),
+    (14, "O....#....\nO.OO#....#\n.....##..?\n", 3, 10),
+    (15, "rn=1,cm-,qp=x\n", 1, 12),
+    (16, ".|...\\....\n|.-.\\..*..\n", 2, 8),
+    (17, "2413432311323\n32154535z5623\n", 2, 9),
+    (18, "R 6 (#70c710)\nX 5 (#0dc571)\n", 2, 1),
+    (19, "in{x<10:ab,R}\nab{s>5:A,zz}\n\n{x=1,m=2,a=3,s=4}\n", 2, 10),
+    (20, "broadcaster -> a\n%a -> b\n&b => a\n", 3, 1),
+    (21, "...\n.S.\n.S.\n", 3, 2),
+    (22, "1,0,1~1,2,1\n0,0,2~2,0,y\n", 2, 11),
+    (23, "#.###\n#...#\n###?#\n", 3, 4),
+    (24, "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1\n", 2, 14),
+    (25, "jqt: rhn xhk\nrhn xhk hfx\n", 2, 1),
+    // inputs that are well formed but too short to solve
+    (10, "7-F7-\n.FJ|7\nSJLL7\n", 3, 1),
+    (15, "", 1, 1),
+    (15, "rn=1,,cm-\n", 1, 6),
+    (15, "\n\n", 2, 1),
+    (16, "\n", 1, 1),
+    (8, "\nAAA = (AAA, AAA)\n", 1, 1),
+    (20, "%a -> b\n%b -> a\n", 3, 1),
+    (23, "#.#\n###\n#.#\n", 3, 2),
+];
+
+#[test]
+fn parse_errors() {
+    let mut failures = Vec::new();
+    for (day, input, line, column) in CASES {
+        let solve = lookup(YEAR, day).expect("No solution for the day").solve;
+        match solve(input, Some(1)) {
+            Ok(_) => failures.push(format!("day{}: parsed without an error", day)),
+            Err(e) if (e.line, e.column) != (line, column) => failures.push(format!(
+                "day{}: expected line {} column {}, got {}",
+                day, line, column, e
+            )),
+            Err(_) => {}
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// part 2 of day 24 picks from 5 hailstones, with fewer there is no answer
+#[test]
+fn too_few_hailstones() {
+    let solve = lookup(YEAR, 24).expect("No solution for the day").solve;
+    let input = "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n";
+    let results = solve(input, Some(2)).unwrap();
+    assert_eq!(results[0].answer, common::Answer::Unsolved);
+}
+
+// inputs that parse but leave a part without an answer, and which part
+const UNSOLVED: [(u32, &str, u32); 8] = [
+    (8, "LR\n", 1),
+    (8, "LR\n", 2),
+    (13, "##\n##\n", 1),
+    (17, "5\n", 1),
+    (17, "12\n34\n", 2),
+    (20, "broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a\n", 2),
+    (23, "#.#\n#^#\n#.#\n", 1),
+    (25, "a: b\nc: d\n", 1),
+];
+
+#[test]
+fn unsolved() {
+    let mut failures = Vec::new();
+    for (day, input, part) in UNSOLVED {
+        let solve = lookup(YEAR, day).expect("No solution for the day").solve;
+        match solve(input, Some(part)) {
+            Ok(results) if results[0].answer == common::Answer::Unsolved => {}
+            Ok(results) => failures.push(format!("day{} part{}: got {:?}", day, part, results[0].answer)),
+            Err(e) => failures.push(format!("day{} part{}: {}", day, part, e)),
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
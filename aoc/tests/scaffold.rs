@@ -0,0 +1,51 @@
+use std::{env, fs, path::PathBuf};
+
+use aoc::{scaffold, workspace_root};
+
+// a copy of the files `generate` edits, so the real workspace stays untouched
+fn scratch_root() -> PathBuf {
+    let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("aoc/src")).unwrap();
+    for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/lib.rs"] {
+        fs::copy(workspace_root().join(file), root.join(file)).unwrap();
+    }
+    root
+}
+
+#[test]
+fn new_days() {
+    let root = scratch_root();
+    let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+
+    let touched = scaffold::generate(&root, 2024, 1).unwrap();
+    assert_eq!(touched.len(), 8);
+    assert!(read("2024/day1/Cargo.toml").contains("name = \"day1-2024\""));
+    assert!(read("2024/day1/src/main.rs").contains("use day1_2024::Solver;"));
+    assert!(read("2024/day1/src/lib.rs").contains("impl Solution for Solver"));
+    assert!(read("2024/day1/src/lib.rs").contains("include_str!(\"../example_input\")"));
+    assert!(read("Cargo.toml").contains("\"2023/day*\", \"2024/day*\"]"));
+    assert!(read("aoc/Cargo.toml").contains(
+        "day25 = { path = \"../2023/day25\" }\nday1-2024 = { path = \"../2024/day1\" }\n\n[lints]"
+    ));
+    assert!(read("aoc/src/lib.rs").contains("pub mod year2023;\npub mod year2024;\n"));
+    assert!(
+        read("aoc/src/lib.rs").contains("        2024 => year2024::day(day),\n        _ => None,")
+    );
+    assert!(read("aoc/src/year2024.rs").contains("    (1, Day::of::<day1_2024::Solver>()),\n];"));
+
+    // the second day of the year only adds itself
+    let touched = scaffold::generate(&root, 2024, 7).unwrap();
+    assert_eq!(touched.len(), 6);
+    assert_eq!(read("Cargo.toml").matches("2024/day*").count(), 1);
+    assert_eq!(read("aoc/src/lib.rs").matches("year2024").count(), 2);
+    assert!(read("aoc/src/year2024.rs").contains(
+        "    (1, Day::of::<day1_2024::Solver>()),\n    (7, Day::of::<day7_2024::Solver>()),\n];"
+    ));
+
+    assert!(scaffold::generate(&root, 2024, 1)
+        .unwrap_err()
+        .contains("already exists"));
+    assert!(scaffold::generate(&root, 2024, 26).is_err());
+    fs::remove_dir_all(root).unwrap();
+}
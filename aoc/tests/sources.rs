@@ -0,0 +1,22 @@
+use std::fs;
+
+use aoc::day_dir;
+use common::{Solution, Source};
+
+// the streaming readers have to agree with parsing the whole text
+fn check<S: Solution>(day: u32) {
+    let text = fs::read_to_string(day_dir(2023, day).join("example_input"))
+        .expect("Failed to read the example");
+    let parsed = S::parse(&text).expect("Failed to parse the example");
+    let reader = Source::Text(&text).open().expect("Failed to open the text");
+    let streamed = S::read(reader).expect("Failed to read the example");
+    assert_eq!(S::part1(&parsed).to_string(), S::part1(&streamed).to_string());
+    assert_eq!(S::part2(&parsed).to_string(), S::part2(&streamed).to_string());
+}
+
+#[test]
+fn streamed_input() {
+    check::<day5::Solver>(5);
+    check::<day11::Solver>(11);
+    check::<day12::Solver>(12);
+}
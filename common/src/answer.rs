@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Int(i128),
+    // printed with 3 decimals, like the day 24 solver always did
+    Float(f64),
+    // the puzzle has no such part, e.g. day 25 part 2
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // pad so the runner can align answers in a table
+        match self {
+            Self::Int(v) => f.pad(&v.to_string()),
+            Self::Float(v) => f.pad(&format!("{:.3}", v)),
+            Self::Unsolved => f.pad("-"),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Self::Int(v as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<f64> for Answer {
+    fn from(v: f64) -> Self {
+        Self::Float(v)
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Self::Unsolved, Into::into)
+    }
+}
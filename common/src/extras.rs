@@ -0,0 +1,60 @@
+use std::cell::RefCell;
+
+use crate::Answer;
+
+// numbers a solver finds on the way to its answer, like cycle lengths, that
+// are worth showing next to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extra {
+    pub name: &'static str,
+    pub values: Vec<Answer>,
+}
+
+thread_local! {
+    // None outside of `collect`, so solvers run from tests or benches don't
+    // pile them up
+    static EXTRAS: RefCell<Option<Vec<Extra>>> = const { RefCell::new(None) };
+}
+
+pub fn record<T: Into<Answer>>(name: &'static str, values: impl IntoIterator<Item = T>) {
+    EXTRAS.with(|extras| {
+        if let Some(extras) = extras.borrow_mut().as_mut() {
+            extras.push(Extra {
+                name,
+                values: values.into_iter().map(Into::into).collect(),
+            });
+        }
+    });
+}
+
+// runs `f` and returns what it recorded on this thread
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<Extra>) {
+    let outer = EXTRAS.with(|extras| extras.replace(Some(Vec::new())));
+    let result = f();
+    let recorded = EXTRAS.with(|extras| extras.replace(outer));
+    (result, recorded.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{collect, record, Extra};
+    use crate::Answer;
+
+    #[test]
+    fn test_collect() {
+        record("ignored", [1]);
+        let (answer, extras) = collect(|| {
+            record("cycles", [3u64, 5]);
+            15
+        });
+        assert_eq!(answer, 15);
+        assert_eq!(
+            extras,
+            vec![Extra {
+                name: "cycles",
+                values: vec![Answer::Int(3), Answer::Int(5)],
+            }]
+        );
+        assert_eq!(collect(|| ()).1, Vec::new());
+    }
+}
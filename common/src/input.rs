@@ -0,0 +1,96 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+};
+
+// where a day reads its puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source<'a> {
+    Path(PathBuf),
+    Stdin,
+    Text(&'a str),
+}
+
+impl<'a> Source<'a> {
+    // `-` is stdin, anything else is a path
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Self::Stdin,
+            _ => Self::Path(PathBuf::from(arg)),
+        }
+    }
+
+    // the first command line argument that isn't an option, `default` when
+    // there is none
+    pub fn from_args(default: &str) -> Self {
+        Self::from_args_with(default, &[])
+    }
+
+    // the same, for days with options of their own that take a value
+    pub fn from_args_with(default: &str, options: &[&str]) -> Self {
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--render" | "--log" => {
+                    args.next();
+                }
+                _ if options.contains(&arg.as_str()) => {
+                    args.next();
+                }
+                "-" => return Self::Stdin,
+                _ if arg.starts_with('-') => {}
+                _ => return Self::from_arg(&arg),
+            }
+        }
+        Self::Path(PathBuf::from(default))
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead + 'a>> {
+        Ok(match self {
+            Self::Path(path) => Box::new(BufReader::new(File::open(path)?)),
+            Self::Stdin => Box::new(io::stdin().lock()),
+            Self::Text(text) => Box::new(text.as_bytes()),
+        })
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut text = String::new();
+        self.open()?.read_to_string(&mut text)?;
+        Ok(text)
+    }
+}
+
+// the value that follows `name` on the command line
+pub fn arg_value(name: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+    }
+    None
+}
+
+// where `--render <path>` asks for a picture of the puzzle, if anywhere
+pub fn render_path() -> Option<PathBuf> {
+    arg_value("--render").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::BufRead;
+
+    use super::Source;
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(Source::from_arg("input"), Source::Path("input".into()));
+        let source = Source::Text("1\n2\n");
+        let lines: Vec<String> = source.open().unwrap().lines().map(|l| l.unwrap()).collect();
+        assert_eq!(lines, vec!["1", "2"]);
+        assert_eq!(source.read_to_string().unwrap(), "1\n2\n");
+    }
+}
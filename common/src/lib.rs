@@ -0,0 +1,13 @@
+mod answer;
+pub mod extras;
+mod input;
+pub mod log;
+mod pool;
+mod rng;
+mod solution;
+
+pub use answer::Answer;
+pub use input::{arg_value, render_path, Source};
+pub use pool::{par_map, set_threads, Pool};
+pub use rng::Rng;
+pub use solution::{lines, parse_number, ParseError, Solution};
@@ -0,0 +1,224 @@
+use std::{
+    cell::Cell,
+    env, fmt,
+    io::{self, Write},
+    sync::OnceLock,
+    time::Instant,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+// what gets written to stderr: errors always, the rest only when asked with -v
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Filter {
+    level: Option<Level>,
+    // crate names like `day5`, empty for all of them
+    targets: Vec<String>,
+}
+
+impl Filter {
+    // -v is info, -vv debug and -vvv trace, `--log day5,day8` keeps only
+    // those days. Returns the filter and the arguments it didn't use
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> (Self, Vec<String>) {
+        let mut verbosity = 0;
+        let mut targets = Vec::new();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--verbose" => verbosity += 1,
+                "--log" => {
+                    if let Some(value) = args.next() {
+                        targets.extend(value.split(',').map(|t| t.trim().to_string()));
+                    }
+                }
+                _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => {
+                    verbosity += arg.len() - 1
+                }
+                _ => rest.push(arg),
+            }
+        }
+        let level = match verbosity {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        };
+        (Self { level, targets }, rest)
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        // an error explains a missing answer, it shows up whatever the filter
+        if level == Level::Error {
+            return true;
+        }
+        let target = target.split("::").next().unwrap_or(target);
+        self.level.is_some_and(|max| level <= max)
+            && (self.targets.is_empty() || self.targets.iter().any(|t| t == target))
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+// only the first call counts, binaries that never call it get the filter
+// from their command line on the first message
+pub fn init(filter: Filter) {
+    let _ = FILTER.set(filter);
+}
+
+fn filter() -> &'static Filter {
+    FILTER.get_or_init(|| Filter::from_args(env::args().skip(1)).0)
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    filter().enabled(level, target)
+}
+
+pub fn log(level: Level, target: &str, args: fmt::Arguments) {
+    if !enabled(level, target) {
+        return;
+    }
+    let target = target.split("::").next().unwrap_or(target);
+    let indent = DEPTH.with(|depth| "  ".repeat(depth.get()));
+    let _ = writeln!(io::stderr().lock(), "[{:<5} {}] {}{}", level, target, indent, args);
+}
+
+// times the scope it lives in, see `span!`
+pub struct Span {
+    target: &'static str,
+    name: &'static str,
+    fields: String,
+    start: Instant,
+}
+
+impl Span {
+    pub fn enter(target: &'static str, name: &'static str, fields: impl FnOnce() -> String) -> Self {
+        let fields = if enabled(Level::Debug, target) {
+            fields()
+        } else {
+            String::new()
+        };
+        log(Level::Trace, target, format_args!("{}{} started", name, fields));
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Self {
+            target,
+            name,
+            fields,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+        log(
+            Level::Debug,
+            self.target,
+            format_args!("{}{} took {:?}", self.name, self.fields, elapsed),
+        );
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Error, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Warn, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Info, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)+))
+    };
+}
+
+// `let _span = span!("parse", day = 5);` logs how long the rest of the scope
+// took at debug level, with the fields
+#[macro_export]
+macro_rules! span {
+    ($name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::log::Span::enter(module_path!(), $name, || {
+            #[allow(unused_mut)]
+            let mut fields = String::new();
+            $(fields.push_str(&format!(" {}={}", stringify!($key), $value));)*
+            fields
+        })
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Filter, Level};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter() {
+        let (filter, rest) = Filter::from_args(args(&["run", "--day", "5"]));
+        assert_eq!(filter, Filter::default());
+        assert_eq!(rest, args(&["run", "--day", "5"]));
+        assert!(!filter.enabled(Level::Warn, "day5"));
+        assert!(filter.enabled(Level::Error, "day5"));
+
+        let (filter, rest) = Filter::from_args(args(&["-vv", "input", "--log", "day5,day8"]));
+        assert_eq!(rest, args(&["input"]));
+        assert!(filter.enabled(Level::Debug, "day5::almanac"));
+        assert!(filter.enabled(Level::Info, "day8"));
+        assert!(!filter.enabled(Level::Trace, "day5"));
+        assert!(!filter.enabled(Level::Info, "day21"));
+        assert!(filter.enabled(Level::Error, "day21"));
+
+        let (filter, _) = Filter::from_args(args(&["-v", "--verbose", "-v", "-"]));
+        assert!(filter.enabled(Level::Trace, "aoc"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    cell::Cell,
+    env,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+// the number of threads the runner was told to use, 0 until it says
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // true on the threads of a pool, the work there is already spread out
+    static ON_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+// every default pool from now on gets `threads`, for `aoc run --jobs`
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads.max(1), Ordering::Relaxed);
+}
+
+// a fixed number of scoped worker threads that take the items one at a time,
+// so a few slow items don't hold up a whole chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pool {
+    threads: usize,
+}
+
+impl Default for Pool {
+    // `set_threads`, or AOC_THREADS, or the number of cores
+    fn default() -> Self {
+        let threads = match THREADS.load(Ordering::Relaxed) {
+            0 => env::var("AOC_THREADS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())),
+            threads => threads,
+        };
+        Self::new(threads)
+    }
+}
+
+impl Pool {
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    // the results are in the order of `items`, whichever thread ran them
+    pub fn map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let threads = self.threads.min(items.len());
+        if threads <= 1 {
+            return items.iter().map(f).collect();
+        }
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        ON_WORKER.with(|on_worker| on_worker.set(true));
+                        let mut done = Vec::new();
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = items.get(idx) else {
+                                break;
+                            };
+                            done.push((idx, f(item)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("Failed to join a worker thread."))
+                .collect()
+        });
+        results.sort_unstable_by_key(|(idx, _)| *idx);
+        results.into_iter().map(|(_, r)| r).collect()
+    }
+}
+
+// on a thread of a pool already, as for the days `aoc run` spreads out, the
+// items run right there instead of multiplying the threads
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if ON_WORKER.with(Cell::get) {
+        return items.iter().map(f).collect();
+    }
+    Pool::default().map(items, f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{par_map, set_threads, Pool};
+    use std::thread;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|x| x * x).collect();
+        for threads in [0, 1, 3, 16] {
+            assert_eq!(Pool::new(threads).map(&items, |x| x * x), expected);
+        }
+        assert_eq!(Pool::new(4).map(&[] as &[u64], |x| *x), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_threads() {
+        let items: Vec<u64> = (0..64).collect();
+        let here = thread::current().id();
+        // `aoc run --jobs 1`
+        set_threads(1);
+        assert_eq!(Pool::default().threads(), 1);
+        assert!(par_map(&items, |_| thread::current().id()).iter().all(|id| *id == here));
+        // a par_map on a worker stays on that worker
+        set_threads(4);
+        let nested = Pool::default().map(&items, |_| {
+            let worker = thread::current().id();
+            par_map(&items, |_| thread::current().id()).iter().all(|id| *id == worker)
+        });
+        assert!(nested.into_iter().all(|same| same));
+    }
+}
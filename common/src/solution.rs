@@ -0,0 +1,106 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+use crate::Answer;
+
+// where the input went wrong, lines and columns are 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    // the whole line is at fault
+    pub fn line(line: usize, text: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(line, 1, text, message)
+    }
+
+    // `needle` is a slice of `line_text`, the column is where it starts
+    pub fn at(line: usize, line_text: &str, needle: &str, message: impl Into<String>) -> Self {
+        let offset = (needle.as_ptr() as usize).wrapping_sub(line_text.as_ptr() as usize);
+        let column = if offset <= line_text.len() {
+            line_text[..offset].chars().count() + 1
+        } else {
+            1
+        };
+        Self::new(line, column, needle, message)
+    }
+
+    // the source failed while reading `line`
+    pub fn io(line: usize, e: io::Error) -> Self {
+        Self::new(line, 1, "", format!("failed to read the input: {}", e))
+    }
+
+    // the input ended before something required showed up
+    pub fn eof(line: usize, message: impl Into<String>) -> Self {
+        Self::new(line, 1, "", message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to parse input at line {} column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " ({:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+// numbered lines, the way an editor counts them
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(idx, line)| (idx + 1, line))
+}
+
+// parse a number found in `line_text`, reporting where it sits on failure
+pub fn parse_number<T: std::str::FromStr>(
+    line: usize,
+    line_text: &str,
+    field: &str,
+) -> Result<T, ParseError> {
+    let field = field.trim();
+    field
+        .parse()
+        .map_err(|_| ParseError::at(line, line_text, field, "expected a number"))
+}
+
+// the shape every day shares, so the runner and the tooling around it
+// don't have to know how each puzzle is solved
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    // days that work line by line override this, the rest read everything first
+    fn read<R: BufRead>(mut reader: R) -> Result<Self::Input, ParseError> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| ParseError::io(text.lines().count() + 1, e))?;
+        Self::parse(&text)
+    }
+
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
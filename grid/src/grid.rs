@@ -0,0 +1,421 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use common::ParseError;
+
+use crate::Direction;
+
+// a cell that can be read from, and drawn back to, one char of the puzzle map
+pub trait Cell: Sized {
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+// lines and columns are 1-based, as shown by an editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    InvalidCell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    RowLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCell {
+                line,
+                column,
+                found,
+            } => write!(f, "line {} column {}: invalid cell {:?}", line, column, found),
+            Self::RowLength {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} cells, found {}",
+                line, expected, found
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+impl From<GridError> for ParseError {
+    fn from(e: GridError) -> Self {
+        match e {
+            GridError::InvalidCell {
+                line,
+                column,
+                found,
+            } => ParseError::new(line, column, found.to_string(), "invalid cell"),
+            GridError::RowLength {
+                line,
+                expected,
+                found,
+            } => ParseError::new(
+                line,
+                expected.min(found) + 1,
+                "",
+                format!("expected {} cells, found {}", expected, found),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            cells: Vec::new(),
+            rows: 0,
+            cols: 0,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in 0..self.cols {
+            for row in 0..self.rows {
+                cells.push(self[Pos::new(row, col)].clone());
+            }
+        }
+        Self {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in 0..self.cols {
+            for row in (0..self.rows).rev() {
+                cells.push(self[Pos::new(row, col)].clone());
+            }
+        }
+        Self {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in (0..self.cols).rev() {
+            for row in 0..self.rows {
+                cells.push(self[Pos::new(row, col)].clone());
+            }
+        }
+        Self {
+            cells,
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    // blank lines are skipped, but still counted for error positions
+    pub fn parse(text: &str) -> Result<Self, GridError> {
+        let mut grid = Self::default();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            grid.parse_line(idx + 1, line)?;
+        }
+        Ok(grid)
+    }
+
+    // `line` is only used to report errors
+    pub fn parse_line(&mut self, line: usize, text: &str) -> Result<(), GridError> {
+        let mut row = Vec::with_capacity(self.cols);
+        for (idx, c) in text.chars().enumerate() {
+            let cell = T::from_char(c).ok_or(GridError::InvalidCell {
+                line,
+                column: idx + 1,
+                found: c,
+            })?;
+            row.push(cell);
+        }
+        self.push_row(line, row)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn push_row(&mut self, line: usize, row: Vec<T>) -> Result<(), GridError> {
+        if self.rows == 0 {
+            self.cols = row.len();
+        } else if row.len() != self.cols {
+            return Err(GridError::RowLength {
+                line,
+                expected: self.cols,
+                found: row.len(),
+            });
+        }
+        self.cells.extend(row);
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    // one step in the direction, None when it leaves the grid
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        let (dr, dc) = direction.offset();
+        let next = Pos::new(
+            pos.row.checked_add_signed(dr)?,
+            pos.col.checked_add_signed(dc)?,
+        );
+        if self.contains(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+        OFFSETS.into_iter().filter_map(move |(dr, dc)| {
+            let next = Pos::new(
+                pos.row.checked_add_signed(dr)?,
+                pos.col.checked_add_signed(dc)?,
+            );
+            if self.contains(next) {
+                Some(next)
+            } else {
+                None
+            }
+        })
+    }
+
+    // the grid repeated infinitely in every direction
+    pub fn wrap(&self, row: i64, col: i64) -> Pos {
+        Pos::new(
+            row.rem_euclid(self.rows as i64) as usize,
+            col.rem_euclid(self.cols as i64) as usize,
+        )
+    }
+
+    pub fn get_wrapped(&self, row: i64, col: i64) -> &T {
+        &self[self.wrap(row, col)]
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        &self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        &mut self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T: Cell> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            let line: String = self.row(row).iter().map(|c| c.to_char()).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cell, Direction, Grid, GridError, Pos};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Open,
+    }
+
+    impl Cell for Tile {
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '#' => Some(Self::Wall),
+                '.' => Some(Self::Open),
+                _ => None,
+            }
+        }
+
+        fn to_char(&self) -> char {
+            match self {
+                Self::Wall => '#',
+                Self::Open => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "#..\n.#.\n";
+        let grid: Grid<Tile> = Grid::parse(text).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[Pos::new(1, 1)], Tile::Wall);
+        assert_eq!(grid.to_string(), text);
+        assert_eq!(grid.rotate_clockwise().to_string(), ".#\n#.\n..\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "..\n.#\n#.\n");
+        assert_eq!(grid.transpose().to_string(), "#.\n.#\n..\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Grid::<Tile>::parse("#.\n.x"),
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            Grid::<Tile>::parse("#.\n\n.#.").unwrap_err(),
+            GridError::RowLength {
+                line: 3,
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, Tile::Open);
+        assert_eq!(grid.neighbours4(Pos::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours4(Pos::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Pos::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.step(Pos::new(0, 0), Direction::North), None);
+        assert_eq!(grid.step(Pos::new(0, 0), Direction::East), Some(Pos::new(0, 1)));
+        assert_eq!(grid.wrap(-1, 4), Pos::new(2, 1));
+    }
+
+    #[test]
+    fn test_direction() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod direction;
+mod grid;
+mod render;
+
+pub use direction::Direction;
+pub use grid::{Cell, Grid, GridError, Pos};
+pub use render::{Picture, Rgb};
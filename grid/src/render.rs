@@ -0,0 +1,316 @@
+use std::{fs, io, path::Path};
+
+use crate::{Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const DARK: Self = Self(40, 44, 52);
+    pub const GREY: Self = Self(120, 120, 120);
+    pub const RED: Self = Self(220, 50, 47);
+    pub const GREEN: Self = Self(80, 180, 80);
+    pub const BLUE: Self = Self(38, 139, 210);
+    pub const YELLOW: Self = Self(240, 200, 40);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// points are (row, col) in cell units, (0.5, 0.5) is the centre of the first cell
+enum Shape {
+    Path(Vec<(f64, f64)>, Rgb),
+    Polygon(Vec<(f64, f64)>, Rgb),
+}
+
+// a picture of a puzzle state, one square per cell with paths and polygons
+// drawn on top, for debugging
+pub struct Picture {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Rgb>,
+    shapes: Vec<Shape>,
+}
+
+impl Picture {
+    pub fn new(rows: usize, cols: usize, background: Rgb) -> Self {
+        Self {
+            rows,
+            cols,
+            cells: vec![background; rows * cols],
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, mut color: impl FnMut(Pos, &T) -> Rgb) -> Self {
+        let mut picture = Self::new(grid.rows(), grid.cols(), Rgb::BLACK);
+        for (pos, cell) in grid.iter() {
+            picture.fill(pos, color(pos, cell));
+        }
+        picture
+    }
+
+    pub fn fill(&mut self, pos: Pos, color: Rgb) {
+        assert!(pos.row < self.rows && pos.col < self.cols, "{:?} is out of the picture", pos);
+        self.cells[pos.row * self.cols + pos.col] = color;
+    }
+
+    // a line through the centres of the cells
+    pub fn path(&mut self, cells: &[Pos], color: Rgb) {
+        let points = cells
+            .iter()
+            .map(|p| (p.row as f64 + 0.5, p.col as f64 + 0.5))
+            .collect();
+        self.shapes.push(Shape::Path(points, color));
+    }
+
+    pub fn polygon(&mut self, corners: &[(f64, f64)], color: Rgb) {
+        self.shapes.push(Shape::Polygon(corners.to_vec(), color));
+    }
+
+    // pixels per cell, so that the larger side ends up around 800 pixels
+    fn scale(&self) -> usize {
+        (800 / self.rows.max(self.cols).max(1)).clamp(1, 16)
+    }
+
+    pub fn to_svg(&self) -> String {
+        let scale = self.scale();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+            self.cols * scale,
+            self.rows * scale,
+            self.cols,
+            self.rows
+        );
+        // one rect per run of equal cells in a row
+        for row in 0..self.rows {
+            let cells = &self.cells[row * self.cols..(row + 1) * self.cols];
+            let mut start = 0;
+            for col in 1..=self.cols {
+                if col == self.cols || cells[col] != cells[start] {
+                    svg.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                        start,
+                        row,
+                        col - start,
+                        cells[start].hex()
+                    ));
+                    start = col;
+                }
+            }
+        }
+        for shape in self.shapes.iter() {
+            let (points, color) = match shape {
+                Shape::Path(points, color) | Shape::Polygon(points, color) => (points, color),
+            };
+            let points: Vec<String> = points.iter().map(|(r, c)| format!("{},{}", c, r)).collect();
+            match shape {
+                Shape::Path(..) => svg.push_str(&format!(
+                    "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" stroke-linejoin=\"round\"/>\n",
+                    points.join(" "),
+                    color.hex()
+                )),
+                Shape::Polygon(..) => svg.push_str(&format!(
+                    "<polygon points=\"{}\" fill=\"{}\" fill-opacity=\"0.6\"/>\n",
+                    points.join(" "),
+                    color.hex()
+                )),
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // row-major RGB pixels, `scale` pixels per cell
+    fn raster(&self) -> (usize, usize, Vec<Rgb>) {
+        let scale = self.scale();
+        let (width, height) = (self.cols * scale, self.rows * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(self.cells[y / scale * self.cols + x / scale]);
+            }
+        }
+        let s = scale as f64;
+        for shape in self.shapes.iter() {
+            match shape {
+                Shape::Polygon(points, color) => {
+                    // even-odd fill, sampled at the pixel centres
+                    for y in 0..height {
+                        let row = (y as f64 + 0.5) / s;
+                        let mut crossings = Vec::new();
+                        for i in 0..points.len() {
+                            let (r1, c1) = points[i];
+                            let (r2, c2) = points[(i + 1) % points.len()];
+                            if (r1 <= row) != (r2 <= row) {
+                                crossings.push(c1 + (row - r1) / (r2 - r1) * (c2 - c1));
+                            }
+                        }
+                        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                        for pair in crossings.chunks(2) {
+                            let [from, to] = pair else {
+                                continue;
+                            };
+                            for x in 0..width {
+                                let col = (x as f64 + 0.5) / s;
+                                if col >= *from && col < *to {
+                                    pixels[y * width + x] = blend(pixels[y * width + x], *color);
+                                }
+                            }
+                        }
+                    }
+                }
+                Shape::Path(points, color) => {
+                    let half = (scale / 6) as isize;
+                    for pair in points.windows(2) {
+                        let ((r1, c1), (r2, c2)) = (pair[0], pair[1]);
+                        let steps = (((r2 - r1).abs().max((c2 - c1).abs())) * s).ceil() as usize;
+                        for i in 0..=steps {
+                            let t = if steps == 0 { 0.0 } else { i as f64 / steps as f64 };
+                            let y = ((r1 + (r2 - r1) * t) * s) as isize;
+                            let x = ((c1 + (c2 - c1) * t) * s) as isize;
+                            for dy in -half..=half {
+                                for dx in -half..=half {
+                                    let (py, px) = (y + dy, x + dx);
+                                    if py >= 0 && px >= 0 && (py as usize) < height && (px as usize) < width {
+                                        pixels[py as usize * width + px as usize] = *color;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        (width, height, pixels)
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height, pixels) = self.raster();
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for p in pixels {
+            ppm.extend([p.0, p.1, p.2]);
+        }
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let (width, height, pixels) = self.raster();
+        // every scanline starts with filter type 0, no filtering
+        let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+        for y in 0..height {
+            raw.push(0);
+            for p in pixels[y * width..(y + 1) * width].iter() {
+                raw.extend([p.0, p.1, p.2]);
+            }
+        }
+        let mut header = Vec::with_capacity(13);
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // 8 bit depth, RGB, default compression, filter and interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    // the format follows the extension: .svg, .ppm or .png
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let data = match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => self.to_svg().into_bytes(),
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown picture format {}", path.display()),
+                ))
+            }
+        };
+        fs::write(path, data)
+    }
+}
+
+fn blend(under: Rgb, over: Rgb) -> Rgb {
+    let mix = |a: u8, b: u8| ((a as u16 * 2 + b as u16 * 3) / 5) as u8;
+    Rgb(mix(under.0, over.0), mix(under.1, over.1), mix(under.2, over.2))
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// a zlib stream of uncompressed deflate blocks, so no compressor is needed
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(0xffff).collect();
+    if blocks.is_empty() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (idx, block) in blocks.iter().enumerate() {
+        out.push((idx + 1 == blocks.len()) as u8);
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(*block);
+    }
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{crc32, Picture, Rgb};
+    use crate::Pos;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+
+    #[test]
+    fn test_picture() {
+        let mut picture = Picture::new(2, 3, Rgb::BLACK);
+        picture.fill(Pos::new(1, 2), Rgb::RED);
+        picture.path(&[Pos::new(0, 0), Pos::new(0, 2)], Rgb::BLUE);
+        let svg = picture.to_svg();
+        assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"#000000\"/>"));
+        assert!(svg.contains("<rect x=\"2\" y=\"1\" width=\"1\" height=\"1\" fill=\"#dc322f\"/>"));
+        assert!(svg.contains("<polyline points=\"0.5,0.5 2.5,0.5\""));
+
+        // 3x2 cells at 16 pixels each
+        let ppm = picture.to_ppm();
+        assert!(ppm.starts_with(b"P6\n48 32\n255\n"));
+        assert_eq!(ppm.len(), 13 + 48 * 32 * 3);
+        let png = picture.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x30\0\0\0\x20"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+}
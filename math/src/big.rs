@@ -0,0 +1,353 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Shl, Shr, Sub},
+    str::FromStr,
+};
+
+use crate::isqrt::Isqrt;
+
+// an unsigned integer of any size, for the numbers that outgrow a u128
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    // base 2^32, least significant first, without zeros at the end so that
+    // every number has one form. Zero has no limbs
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected decimal digits")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // how many bits it takes, 0 for zero
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() as u64 * 32 - top.leading_zeros() as u64,
+        }
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0;
+        for (idx, &limb) in self.limbs.iter().enumerate() {
+            let rhs = other.limbs.get(idx).copied().unwrap_or(0) as i64;
+            let mut diff = limb as i64 - rhs - borrow;
+            borrow = (diff < 0) as i64;
+            diff += borrow << 32;
+            limbs.push(diff as u32);
+        }
+        Some(Self::normalized(limbs))
+    }
+
+    fn normalized(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    // self * factor + carry, for the decimal conversions
+    fn mul_add_small(&self, factor: u32, carry: u32) -> Self {
+        let mut carry = carry as u64;
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        for &limb in self.limbs.iter() {
+            let v = limb as u64 * factor as u64 + carry;
+            limbs.push(v as u32);
+            carry = v >> 32;
+        }
+        limbs.push(carry as u32);
+        Self::normalized(limbs)
+    }
+
+    // the quotient and the remainder
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut rem = 0u64;
+        let mut limbs = vec![0; self.limbs.len()];
+        for (idx, &limb) in self.limbs.iter().enumerate().rev() {
+            let v = (rem << 32) | limb as u64;
+            limbs[idx] = (v / divisor as u64) as u32;
+            rem = v % divisor as u64;
+        }
+        (Self::normalized(limbs), rem as u32)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        Self::normalized((0..4).map(|idx| (value >> (idx * 32)) as u32).collect())
+    }
+}
+
+impl TryFrom<&BigUint> for u128 {
+    type Error = ();
+
+    fn try_from(value: &BigUint) -> Result<Self, Self::Error> {
+        if value.limbs.len() > 4 {
+            return Err(());
+        }
+        Ok(value
+            .limbs
+            .iter()
+            .rev()
+            .fold(0, |acc, &limb| (acc << 32) | limb as u128))
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+        // nine digits at a time still fit in a u32
+        let mut value = Self::zero();
+        for chunk in s.as_bytes().chunks(9) {
+            let digits = chunk.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32);
+            value = value.mul_add_small(10u32.pow(chunk.len() as u32), digits);
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_small(1_000_000_000);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+        let mut text = chunks.last().copied().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev().skip(1) {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &text)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for BigUint {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for idx in 0..len {
+            let v = self.limbs.get(idx).copied().unwrap_or(0) as u64
+                + other.limbs.get(idx).copied().unwrap_or(0) as u64
+                + carry;
+            limbs.push(v as u32);
+            carry = v >> 32;
+        }
+        limbs.push(carry as u32);
+        Self::normalized(limbs)
+    }
+}
+
+// panics below zero like the primitive integers, see `checked_sub`
+impl Sub for BigUint {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(&other)
+            .unwrap_or_else(|| panic!("{} - {} is below zero", self, other))
+    }
+}
+
+impl Mul for BigUint {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let v = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = v as u32;
+                carry = v >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        Self::normalized(limbs)
+    }
+}
+
+impl Shl<u32> for BigUint {
+    type Output = Self;
+
+    fn shl(self, shift: u32) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+        let (words, bits) = ((shift / 32) as usize, shift % 32);
+        let mut limbs = vec![0; words];
+        let mut carry = 0;
+        for &limb in self.limbs.iter() {
+            limbs.push((limb << bits) | carry);
+            carry = if bits == 0 { 0 } else { limb >> (32 - bits) };
+        }
+        limbs.push(carry);
+        Self::normalized(limbs)
+    }
+}
+
+impl Shr<u32> for BigUint {
+    type Output = Self;
+
+    fn shr(self, shift: u32) -> Self {
+        let (words, bits) = ((shift / 32) as usize, shift % 32);
+        let limbs = self.limbs.get(words..).unwrap_or_default();
+        let shifted = (0..limbs.len())
+            .map(|idx| {
+                let high = match (bits, limbs.get(idx + 1)) {
+                    (0, _) | (_, None) => 0,
+                    (_, Some(&next)) => next << (32 - bits),
+                };
+                (limbs[idx] >> bits) | high
+            })
+            .collect();
+        Self::normalized(shifted)
+    }
+}
+
+impl Isqrt for BigUint {
+    // one bit of the root at a time, from the top
+    fn isqrt(&self) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+        let mut rem = self.clone();
+        let mut root = Self::zero();
+        let mut shift = ((self.bits() - 1) & !1) as u32;
+        loop {
+            let bit = Self::from(1u64) << shift;
+            let candidate = root.clone() + bit.clone();
+            root = match rem.checked_sub(&candidate) {
+                Some(left) => {
+                    rem = left;
+                    (root >> 1) + bit
+                }
+                None => root >> 1,
+            };
+            if shift == 0 {
+                return root;
+            }
+            shift -= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BigUint;
+    use crate::isqrt::Isqrt;
+    use common::Rng;
+
+    fn big(text: &str) -> BigUint {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut rng = Rng::new(25);
+        for _ in 0..1000 {
+            let (a, b) = (rng.next_u64() as u128, rng.next_u64() as u128);
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!(x.clone() * y.clone(), BigUint::from(a * b));
+            assert_eq!(x.clone() + y.clone(), BigUint::from(a + b));
+            assert_eq!(x.clone().checked_sub(&y), a.checked_sub(b).map(BigUint::from));
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            assert_eq!((x.clone() << 37) >> 40, BigUint::from((a << 37) >> 40));
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(u128::try_from(&(x * y)), Ok(a * b));
+        }
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(big("000123"), BigUint::from(123u64));
+        assert_eq!(format!("{:>5}", big("42")), "   42");
+        assert!("12a".parse::<BigUint>().is_err());
+        assert!("".parse::<BigUint>().is_err());
+
+        // past a u128
+        let huge = big("340282366920938463463374607431768211456");
+        assert_eq!(huge, BigUint::from(u128::MAX) + BigUint::from(1u64));
+        assert_eq!(huge.bits(), 129);
+        assert_eq!(u128::try_from(&huge), Err(()));
+        let square = huge.clone() * huge.clone();
+        assert_eq!(
+            square.to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        );
+        assert_eq!(square.clone() - huge.clone() * huge.clone(), BigUint::zero());
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let mut rng = Rng::new(26);
+        for _ in 0..200 {
+            // roots of 1 to 8 limbs
+            let digits: String = (0..rng.range(1, 78))
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            let r = big(&digits);
+            let square = r.clone() * r.clone();
+            assert_eq!(square.isqrt(), r);
+            if !r.is_zero() {
+                let below = square.clone() - BigUint::from(1u64);
+                assert_eq!(below.isqrt(), r.clone() - BigUint::from(1u64));
+            }
+            let next = r.clone() + BigUint::from(1u64);
+            let between = square + r.clone() + r.clone();
+            assert_eq!(between.isqrt(), r);
+            assert!(next.clone() * next > between);
+        }
+        for n in 0..300u128 {
+            assert_eq!(BigUint::from(n).isqrt(), BigUint::from(u128::isqrt(n)));
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::gcd::gcd_u128;
+
+// the x in 0..m that satisfies every x = residue (mod modulus), with m the lcm
+// of the moduli, which don't need to be coprime. None when the congruences
+// contradict each other or m does not fit in a u64
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    let (x, m) = crt_u128(
+        congruences
+            .into_iter()
+            .map(|(residue, modulus)| (residue as u128, modulus as u128)),
+    )?;
+    Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?))
+}
+
+// same as `crt`, None when m does not fit in a u128
+pub fn crt_u128(congruences: impl IntoIterator<Item = (u128, u128)>) -> Option<(u128, u128)> {
+    let (mut x, mut m) = (0_u128, 1_u128);
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus of {} is 0", residue);
+        let residue = residue % modulus;
+        let g = gcd_u128(m, modulus);
+        // m * k = residue - x (mod modulus)
+        let diff = add_mod(residue, (modulus - x % modulus) % modulus, modulus);
+        if !diff.is_multiple_of(g) {
+            return None;
+        }
+        let step = modulus / g;
+        let next = m.checked_mul(step)?;
+        // m / g and step are coprime, the inverse is always there
+        let inverse = inverse_mod(m / g % step, step)?;
+        let k = mul_mod(diff / g % step, inverse, step);
+        // below the next m, which fits
+        x += m * k;
+        m = next;
+    }
+    Some((x, m))
+}
+
+// (a + b) % m for a and b below m, when m is past 2^127 a + b doesn't fit
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn sub_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+// (a * b) % m for a and b below m, doubling and adding so that nothing goes
+// past m
+fn mul_mod(mut a: u128, mut b: u128, m: u128) -> u128 {
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+// the x in 0..m with a * x = 1 (mod m), euclid with the coefficients kept
+// modulo m so they never go negative
+fn inverse_mod(a: u128, m: u128) -> Option<u128> {
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut t0, mut t1) = (0, 1 % m);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, sub_mod(t0, mul_mod(q % m, t1, m), m));
+    }
+    (r0 == 1).then_some(t0)
+}
+
+#[cfg(test)]
+mod test {
+    use common::Rng;
+
+    use super::{crt, crt_u128};
+    use crate::lcm;
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(5, 1 << 40), (0, 3 << 40)]), None);
+        assert_eq!(crt([(0, u64::MAX), (0, u64::MAX - 1)]), None);
+        assert_eq!(
+            crt_u128([(0, u64::MAX as u128), (0, u64::MAX as u128 - 1)]),
+            Some((0, u64::MAX as u128 * (u64::MAX as u128 - 1)))
+        );
+    }
+
+    // (2^64 - 1) * (2^64 + 1) is u128::MAX, the steps after it add up past
+    // it when they aren't reduced
+    #[test]
+    fn test_crt_near_max() {
+        let (a, b) = (u64::MAX as u128, u64::MAX as u128 + 2);
+        let (x, m) = crt_u128([(5, a), (7, b)]).unwrap();
+        assert_eq!((x % a, x % b, m), (5, 7, u128::MAX));
+        assert_eq!(crt_u128([(5, a), (7, b), (x, u128::MAX)]), Some((x, m)));
+        assert_eq!(crt_u128([(5, a), (7, b), (x + 1, u128::MAX)]), None);
+        assert_eq!(crt_u128([(5, a), (7, b), (u128::MAX - 1, u128::MAX)]), None);
+
+        // steps past 2^64 and moduli past 2^127
+        let big = (1 << 100) + 1;
+        assert_eq!(
+            crt_u128([(1, 3), ((1 << 100) - 4, big)]),
+            Some((3802951800684688204490109616126, 3 * big))
+        );
+        assert_eq!(crt_u128([(5, u128::MAX)]), Some((5, u128::MAX)));
+        assert_eq!(crt_u128([(5, 1 << 127 | 1)]), Some((5, 1 << 127 | 1)));
+        assert_eq!(crt_u128([(1, 1 << 127), (2, 3)]), None);
+    }
+
+    // small moduli against trying every x
+    #[test]
+    fn test_crt_brute_force() {
+        let mut rng = Rng::new(10);
+        for _ in 0..2000 {
+            let congruences: Vec<(u64, u64)> = (0..rng.range(1, 4))
+                .map(|_| {
+                    let modulus = rng.range(1, 30);
+                    (rng.range(0, 60), modulus)
+                })
+                .collect();
+            let m = congruences.iter().fold(1, |cur, (_, v)| lcm(cur, *v));
+            let expected = (0..m).find(|x| congruences.iter().all(|(r, n)| x % n == r % n));
+            assert_eq!(
+                crt(congruences.iter().copied()),
+                expected.map(|x| (x, m)),
+                "{:?}",
+                congruences
+            );
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// the largest r with r * r <= n, found exactly without going through floats.
+// A trait so that solvers can be written once for u128 and `BigUint`
+pub trait Isqrt {
+    fn isqrt(&self) -> Self;
+}
+
+impl Isqrt for u128 {
+    fn isqrt(&self) -> Self {
+        u128::isqrt(*self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Isqrt;
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..1000u128 {
+            let r = Isqrt::isqrt(&n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({}) = {}", n, r);
+        }
+        assert_eq!(Isqrt::isqrt(&u128::MAX), u64::MAX as u128);
+    }
+}
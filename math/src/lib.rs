@@ -0,0 +1,12 @@
+mod big;
+mod crt;
+mod gcd;
+mod isqrt;
+
+pub use big::{BigUint, ParseBigUintError};
+pub use crt::{crt, crt_u128};
+pub use gcd::{
+    checked_lcm, checked_lcm_all, checked_lcm_u128, extended_gcd, gcd, gcd_u128, lcm, lcm_all,
+    mod_inverse,
+};
+pub use isqrt::Isqrt;